@@ -0,0 +1,41 @@
+/// What an inline button under the overview message asks for. The action
+/// and the token address travel together in the button's callback data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverviewAction {
+    Refresh,
+    Holders,
+    Audit,
+    PriceInApe,
+}
+
+impl OverviewAction {
+    fn tag(self) -> &'static str {
+        match self {
+            OverviewAction::Refresh => "refresh",
+            OverviewAction::Holders => "holders",
+            OverviewAction::Audit => "audit",
+            OverviewAction::PriceInApe => "ape",
+        }
+    }
+
+    /// Callback data for this action on `token_address`. Stays within
+    /// Telegram's 64-byte limit for a 42-character address.
+    pub fn callback_data(self, token_address: &str) -> String {
+        format!("{}:{}", self.tag(), token_address)
+    }
+
+    /// Parses data produced by [`OverviewAction::callback_data`] back into
+    /// the action and the token address.
+    pub fn parse(data: &str) -> Option<(Self, &str)> {
+        let (tag, token_address) = data.split_once(':')?;
+        let action = [
+            OverviewAction::Refresh,
+            OverviewAction::Holders,
+            OverviewAction::Audit,
+            OverviewAction::PriceInApe,
+        ]
+        .into_iter()
+        .find(|action| action.tag() == tag)?;
+        Some((action, token_address))
+    }
+}
@@ -0,0 +1,238 @@
+use std::fmt;
+use std::str::FromStr;
+
+use tiny_keccak::{Hasher, Keccak};
+
+/// A 20-byte account or contract address. Displays in lowercase, the form
+/// used for every lookup, URL and cache key; [`Address::checksummed`] gives
+/// the EIP-55 form for showing to people.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Address([u8; 20]);
+
+/// Whether the letter case of a parsed address carried an EIP-55 checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checksum {
+    /// All lowercase or all uppercase, so there was nothing to check.
+    Absent,
+    Valid,
+    /// Mixed case that does not match; the address may be mistyped.
+    Invalid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressError {
+    /// Not `0x` followed by the expected number of characters.
+    Length,
+    /// Contains something other than hex digits.
+    NotHex,
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressError::Length => write!(f, "wrong length for 0x-prefixed hex"),
+            AddressError::NotHex => write!(f, "contains non-hex characters"),
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+impl Address {
+    /// Parses `0x` followed by 40 hex digits in any case, and reports
+    /// whether mixed case matched the EIP-55 checksum. A mismatch is not an
+    /// error; the caller decides whether to warn.
+    pub fn parse(text: &str) -> Result<(Self, Checksum), AddressError> {
+        let mut bytes = [0; 20];
+        decode_hex(text, &mut bytes)?;
+        let address = Self(bytes);
+        let hex = &text[2..];
+
+        let has_lower = hex.bytes().any(|c| c.is_ascii_lowercase());
+        let has_upper = hex.bytes().any(|c| c.is_ascii_uppercase());
+        let checksum = if !(has_lower && has_upper) {
+            Checksum::Absent
+        } else if address.checksummed()[2..] == *hex {
+            Checksum::Valid
+        } else {
+            Checksum::Invalid
+        };
+        Ok((address, checksum))
+    }
+
+    /// The EIP-55 mixed-case form, e.g. `0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed`.
+    pub fn checksummed(&self) -> String {
+        let lower = self.to_string();
+        let mut hasher = Keccak::v256();
+        hasher.update(&lower.as_bytes()[2..]);
+        let mut hash = [0; 32];
+        hasher.finalize(&mut hash);
+
+        let mut checksummed = String::from("0x");
+        for (index, c) in lower[2..].chars().enumerate() {
+            let nibble = (hash[index / 2] >> (if index % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                checksummed.push(c.to_ascii_uppercase());
+            } else {
+                checksummed.push(c);
+            }
+        }
+        checksummed
+    }
+}
+
+/// A 32-byte transaction hash. Displays in lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TxHash([u8; 32]);
+
+impl FromStr for TxHash {
+    type Err = AddressError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0; 32];
+        decode_hex(text, &mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for TxHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+/// Decodes `0x`-prefixed hex of exactly `bytes.len()` bytes, in any case.
+fn decode_hex(text: &str, bytes: &mut [u8]) -> Result<(), AddressError> {
+    let hex = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .filter(|hex| hex.len() == bytes.len() * 2)
+        .ok_or(AddressError::Length)?;
+    if !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AddressError::NotHex);
+    }
+    for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|_| AddressError::NotHex)?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| AddressError::NotHex)?;
+    }
+    Ok(())
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    write!(f, "0x")?;
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+/// Parses an address, ignoring its checksum.
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text).map(|(address, _)| address)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+/// The checksummed form of `address` if it parses, otherwise `address` as
+/// given.
+pub fn checksummed(address: &str) -> String {
+    address
+        .parse::<Address>()
+        .map(|address| address.checksummed())
+        .unwrap_or_else(|_| address.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference vectors from EIP-55.
+    const CHECKSUMMED: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    fn checksums_reference_vectors() {
+        for expected in CHECKSUMMED {
+            let address: Address = expected.to_lowercase().parse().unwrap();
+            assert_eq!(address.checksummed(), expected);
+            assert_eq!(checksummed(&expected.to_lowercase()), expected);
+        }
+    }
+
+    #[test]
+    fn valid_checksum() {
+        for text in CHECKSUMMED {
+            let (address, checksum) = Address::parse(text).unwrap();
+            assert_eq!(checksum, Checksum::Valid);
+            assert_eq!(address.to_string(), text.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn single_case_has_no_checksum() {
+        let text = CHECKSUMMED[0];
+        let lower = text.to_lowercase();
+        let upper = format!("0x{}", text[2..].to_uppercase());
+        for text in [lower.as_str(), upper.as_str()] {
+            let (address, checksum) = Address::parse(text).unwrap();
+            assert_eq!(checksum, Checksum::Absent);
+            assert_eq!(address.to_string(), lower);
+        }
+    }
+
+    #[test]
+    fn invalid_checksum_still_parses() {
+        // The first letter of a reference vector with its case flipped.
+        let mistyped = "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let (address, checksum) = Address::parse(mistyped).unwrap();
+        assert_eq!(checksum, Checksum::Invalid);
+        assert_eq!(address.checksummed(), CHECKSUMMED[0]);
+        assert_eq!(mistyped.parse::<Address>(), Ok(address));
+    }
+
+    #[test]
+    fn rejects_malformed_addresses() {
+        let short = &CHECKSUMMED[0][..41];
+        let long = format!("{}0", CHECKSUMMED[0]);
+        let unprefixed = &CHECKSUMMED[0][2..];
+        assert_eq!(Address::parse(short), Err(AddressError::Length));
+        assert_eq!(Address::parse(&long), Err(AddressError::Length));
+        assert_eq!(Address::parse(unprefixed), Err(AddressError::Length));
+        assert_eq!(
+            Address::parse("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"),
+            Err(AddressError::NotHex)
+        );
+        assert_eq!(
+            Address::parse("0x+aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Err(AddressError::NotHex)
+        );
+        assert_eq!(checksummed("not an address"), "not an address");
+    }
+
+    #[test]
+    fn tx_hash_round_trips() {
+        let hash = "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b";
+        assert_eq!(hash.parse::<TxHash>().unwrap().to_string(), hash);
+        let upper = format!("0X{}", hash[2..].to_uppercase());
+        assert_eq!(upper.parse::<TxHash>().unwrap().to_string(), hash);
+    }
+
+    #[test]
+    fn rejects_malformed_tx_hashes() {
+        assert_eq!(CHECKSUMMED[0].parse::<TxHash>(), Err(AddressError::Length));
+        let not_hex = format!("0x{}", "g".repeat(64));
+        assert_eq!(not_hex.parse::<TxHash>(), Err(AddressError::NotHex));
+    }
+}
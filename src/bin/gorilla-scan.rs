@@ -0,0 +1,88 @@
+//! Command-line scanner: runs the same fetch pipeline as the Telegram bot and
+//! prints the result to stdout.
+//!
+//! Usage: `gorilla-scan scan <token address> [--json]`
+
+use std::env;
+use std::process::ExitCode;
+
+use dotenv::dotenv;
+use gorilla_scan::address::{Address, Checksum};
+use gorilla_scan::config::Config;
+use gorilla_scan::render;
+use gorilla_scan::report::analyze;
+use gorilla_scan::scan::Scanner;
+use log::warn;
+
+const USAGE: &str = "Usage: gorilla-scan scan <token address> [--json]";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenv().ok();
+    pretty_env_logger::init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let json = args.iter().any(|arg| arg == "--json");
+    let positional: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    let token_address = match positional.as_slice() {
+        ["scan", token_address] => *token_address,
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let token_address = match Address::parse(token_address) {
+        Ok((address, Checksum::Invalid)) => {
+            eprintln!("Warning: {token_address} does not match its EIP-55 checksum");
+            address
+        }
+        Ok((address, _)) => address,
+        Err(e) => {
+            eprintln!("Invalid token address {token_address}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Configuration error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let scanner = Scanner::from_config(&config);
+    if let Err(e) = scanner
+        .native_price
+        .refresh(&scanner.ape_express, config.native_price.timeout)
+        .await
+    {
+        warn!("Error fetching native token price: {}", e);
+    }
+
+    let scan = match scanner.scan(&token_address).await {
+        Ok(scan) => scan,
+        Err(e) => {
+            eprintln!("Error scanning {token_address}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let overview = analyze(&scan, &config.links);
+
+    if json {
+        match render::json(&overview) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("Error encoding JSON: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        print!("{}", render::plain_text(&overview));
+    }
+    ExitCode::SUCCESS
+}
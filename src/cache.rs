@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::OnceCell;
+use tokio::time::Instant;
+
+use crate::client::FetchError;
+use crate::config::CacheTtls;
+use crate::token_audit::TokenAudit;
+use crate::token_holders::TokenTopHolders;
+use crate::token_info::TokenInfo;
+use crate::token_overview::TokenOverview;
+use crate::token_price_history::TokenPriceHistory;
+use crate::wallet_holdings::WalletHoldings;
+
+/// Entries beyond this count trigger a sweep of expired entries on insert.
+const SWEEP_THRESHOLD: usize = 1024;
+
+/// A fetch that is currently running, shared by every caller asking for the
+/// same key until it completes.
+type InFlight<V> = Arc<OnceCell<Result<V, FetchError>>>;
+
+/// In-process cache whose entries expire a fixed time after insertion.
+///
+/// Concurrent misses for the same key are coalesced: only the first caller
+/// runs its fetch and everyone else waits for and receives the same result.
+#[derive(Debug)]
+pub struct TtlCache<V> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, V)>>,
+    in_flight: Mutex<HashMap<String, InFlight<V>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl<V: Clone> TtlCache<V> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the live entry for `key`, counting the lookup as a hit or miss.
+    pub fn get(&self, key: &str) -> Option<V> {
+        let entries = self.entries.lock().unwrap();
+        match entries.get(&normalize_key(key)) {
+            Some((inserted_at, value)) if inserted_at.elapsed() < self.ttl => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(value.clone())
+            }
+            _ => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub fn insert(&self, key: &str, value: V) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= SWEEP_THRESHOLD {
+            let ttl = self.ttl;
+            entries.retain(|_, (inserted_at, _)| inserted_at.elapsed() < ttl);
+        }
+        entries.insert(normalize_key(key), (Instant::now(), value));
+    }
+
+    /// Serves `key` from the cache, or awaits `fetch` and caches its result.
+    /// If another caller is already fetching `key`, waits for that fetch
+    /// instead of starting a new one. Errors are shared but never cached.
+    pub async fn get_or_try_insert_with(
+        &self,
+        key: &str,
+        fetch: impl Future<Output = Result<V, FetchError>>,
+    ) -> Result<V, FetchError> {
+        if let Some(value) = self.get(key) {
+            return Ok(value);
+        }
+
+        let key = normalize_key(key);
+        let flight = self
+            .in_flight
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default()
+            .clone();
+
+        let result = flight
+            .get_or_init(|| async {
+                let result = fetch.await;
+                if let Ok(value) = &result {
+                    self.insert(&key, value.clone());
+                }
+                result
+            })
+            .await
+            .clone();
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight
+            .get(&key)
+            .is_some_and(|current| Arc::ptr_eq(current, &flight))
+        {
+            in_flight.remove(&key);
+        }
+        result
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} hits / {} misses", self.hits, self.misses)
+    }
+}
+
+/// Token addresses are case-insensitive, so every key is lowercased.
+fn normalize_key(key: &str) -> String {
+    key.to_lowercase()
+}
+
+/// One cache per upstream data source used by a scan or wallet lookup.
+#[derive(Debug)]
+pub struct ScanCache {
+    pub token_info: TtlCache<TokenInfo>,
+    pub token_overview: TtlCache<TokenOverview>,
+    pub price_history: TtlCache<TokenPriceHistory>,
+    pub holders: TtlCache<TokenTopHolders>,
+    pub audit: TtlCache<TokenAudit>,
+    pub wallet_holdings: TtlCache<WalletHoldings>,
+}
+
+impl ScanCache {
+    pub fn new(ttls: &CacheTtls) -> Self {
+        Self {
+            token_info: TtlCache::new(ttls.token_info),
+            token_overview: TtlCache::new(ttls.token_overview),
+            price_history: TtlCache::new(ttls.price_history),
+            holders: TtlCache::new(ttls.holders),
+            audit: TtlCache::new(ttls.audit),
+            wallet_holdings: TtlCache::new(ttls.wallet_holdings),
+        }
+    }
+}
+
+impl fmt::Display for ScanCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "token info: {}, token overview: {}, price history: {}, holders: {}, audit: {}, wallet holdings: {}",
+            self.token_info.stats(),
+            self.token_overview.stats(),
+            self.price_history.stats(),
+            self.holders.stats(),
+            self.audit.stats(),
+            self.wallet_holdings.stats()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use futures::future::join_all;
+
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    /// A fetch that takes a second, counting how often it actually runs.
+    async fn counted_fetch(
+        runs: &AtomicUsize,
+        result: Result<u32, FetchError>,
+    ) -> Result<u32, FetchError> {
+        runs.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        result
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn concurrent_lookups_share_one_fetch() {
+        let cache = TtlCache::new(TTL);
+        let runs = AtomicUsize::new(0);
+        let results = join_all(
+            (0..5).map(|n| cache.get_or_try_insert_with("0xABC", counted_fetch(&runs, Ok(n)))),
+        )
+        .await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        let first = *results[0].as_ref().unwrap();
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Ok(n) if *n == first)));
+        assert_eq!(cache.get("0xabc"), Some(first));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn errors_are_shared_but_not_cached() {
+        let cache = TtlCache::new(TTL);
+        let runs = AtomicUsize::new(0);
+        let results = join_all((0..3).map(|_| {
+            cache.get_or_try_insert_with("0xabc", counted_fetch(&runs, Err(FetchError::Timeout)))
+        }))
+        .await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Err(FetchError::Timeout))));
+
+        let result = cache
+            .get_or_try_insert_with("0xabc", counted_fetch(&runs, Ok(7)))
+            .await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert!(matches!(result, Ok(7)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn entries_expire_after_the_ttl() {
+        let cache = TtlCache::new(TTL);
+        cache.insert("0xabc", 1);
+        tokio::time::advance(TTL - Duration::from_secs(1)).await;
+        assert_eq!(cache.get("0xabc"), Some(1));
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_eq!(cache.get("0xabc"), None);
+
+        let runs = AtomicUsize::new(0);
+        let result = cache
+            .get_or_try_insert_with("0xabc", counted_fetch(&runs, Ok(2)))
+            .await;
+        assert!(matches!(result, Ok(2)));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lookups_count_hits_and_misses() {
+        let cache = TtlCache::new(TTL);
+        assert_eq!(cache.get("0xabc"), None);
+        let runs = AtomicUsize::new(0);
+        for _ in 0..3 {
+            let result = cache
+                .get_or_try_insert_with("0xABC", counted_fetch(&runs, Ok(1)))
+                .await;
+            assert!(matches!(result, Ok(1)));
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+    }
+}
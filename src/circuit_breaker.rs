@@ -0,0 +1,191 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use log::warn;
+
+use crate::client::FetchError;
+use crate::config::CircuitBreakerConfig;
+
+/// Per-upstream circuit breaker.
+///
+/// After `failure_threshold` consecutive transient failures the circuit opens
+/// and calls fail fast with [`FetchError::CircuitOpen`]. Once `cooldown` has
+/// passed a single probe call is let through: success closes the circuit,
+/// failure reopens it for another cooldown. An attempt that takes longer
+/// than `attempt_timeout` fails with [`FetchError::Timeout`], so an upstream
+/// that hangs opens the circuit just like one that errors.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: &'static str,
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probing: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &'static str, config: CircuitBreakerConfig) -> Self {
+        Self {
+            name,
+            config,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Runs `request` unless the circuit is open, giving up on it after
+    /// `attempt_timeout`. Only transient failures, including that timeout,
+    /// count against the circuit. A request dropped before completing (for
+    /// example by a caller's shorter timeout) counts neither way; if it was
+    /// the probe, the next call may probe instead.
+    pub async fn call<T>(
+        &self,
+        request: impl Future<Output = Result<T, FetchError>>,
+    ) -> Result<T, FetchError> {
+        self.try_acquire()?;
+
+        let mut attempt = Attempt {
+            breaker: self,
+            finished: false,
+        };
+        let result = tokio::time::timeout(self.config.attempt_timeout, request)
+            .await
+            .unwrap_or(Err(FetchError::Timeout));
+        attempt.finished = true;
+        match &result {
+            Err(e) if e.is_transient() => self.record_failure(),
+            _ => self.record_success(),
+        }
+        result
+    }
+
+    fn try_acquire(&self) -> Result<(), FetchError> {
+        let mut state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => Ok(()),
+            Some(opened_at) if opened_at.elapsed() >= self.config.cooldown && !state.probing => {
+                state.probing = true;
+                Ok(())
+            }
+            Some(_) => Err(FetchError::CircuitOpen),
+        }
+    }
+
+    fn release_probe(&self) {
+        self.state.lock().unwrap().probing = false;
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.opened_at.is_some() {
+            warn!("Circuit for {} closed", self.name);
+        }
+        *state = State::default();
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        if state.probing || state.consecutive_failures >= self.config.failure_threshold {
+            if state.opened_at.is_none() {
+                warn!(
+                    "Circuit for {} opened after {} consecutive failures",
+                    self.name, state.consecutive_failures
+                );
+            }
+            state.opened_at = Some(Instant::now());
+            state.probing = false;
+        }
+    }
+}
+
+/// Gives up the probe slot if the call it guards is dropped before finishing.
+struct Attempt<'a> {
+    breaker: &'a CircuitBreaker,
+    finished: bool,
+}
+
+impl Drop for Attempt<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.breaker.release_probe();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(
+            "test",
+            CircuitBreakerConfig {
+                failure_threshold: 1,
+                cooldown: Duration::from_secs(30),
+                attempt_timeout: Duration::from_secs(2),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn transient_failure_opens_the_circuit() {
+        let breaker = breaker();
+        let result: Result<(), _> = breaker.call(async { Err(FetchError::Timeout) }).await;
+        assert!(matches!(result, Err(FetchError::Timeout)));
+        let result = breaker.call(async { Ok(()) }).await;
+        assert!(matches!(result, Err(FetchError::CircuitOpen)));
+    }
+
+    #[tokio::test]
+    async fn permanent_failure_keeps_the_circuit_closed() {
+        let breaker = breaker();
+        let result: Result<(), _> = breaker.call(async { Err(FetchError::NotFound) }).await;
+        assert!(matches!(result, Err(FetchError::NotFound)));
+        assert!(breaker.call(async { Ok(()) }).await.is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn hanging_request_opens_the_circuit() {
+        let breaker = breaker();
+        let hung = breaker.call(std::future::pending::<Result<(), FetchError>>());
+        assert!(matches!(hung.await, Err(FetchError::Timeout)));
+        let result = breaker.call(async { Ok(()) }).await;
+        assert!(matches!(result, Err(FetchError::CircuitOpen)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_call_is_not_a_failure() {
+        let breaker = breaker();
+        let pending = breaker.call(std::future::pending::<Result<(), FetchError>>());
+        assert!(tokio::time::timeout(Duration::from_secs(1), pending)
+            .await
+            .is_err());
+        assert!(breaker.call(async { Ok(()) }).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn cancelled_probe_lets_the_next_call_probe() {
+        let breaker = CircuitBreaker::new(
+            "test",
+            CircuitBreakerConfig {
+                failure_threshold: 1,
+                cooldown: Duration::ZERO,
+                attempt_timeout: Duration::from_secs(2),
+            },
+        );
+        let _: Result<(), _> = breaker.call(async { Err(FetchError::Timeout) }).await;
+        let probe = breaker.call(std::future::pending::<Result<(), FetchError>>());
+        assert!(tokio::time::timeout(Duration::from_millis(1), probe)
+            .await
+            .is_err());
+        assert!(breaker.call(async { Ok(()) }).await.is_ok());
+    }
+}
@@ -0,0 +1,358 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+use crate::address::{Address, TxHash};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::config::{Config, RetryConfig};
+use crate::native_token::NativeToken;
+use crate::pool_info::PoolInfo;
+use crate::rate_limit::RateLimiter;
+use crate::retry::with_retry;
+use crate::rpc::{RpcReceipt, RpcResponse, RpcTransaction};
+use crate::token_amount::TokenAmount;
+use crate::token_audit::TokenAudit;
+use crate::token_holders::TokenTopHolders;
+use crate::token_info::TokenInfo;
+use crate::token_overview::TokenOverview;
+use crate::token_price_history::TokenPriceHistory;
+use crate::wallet_holdings::WalletHoldings;
+
+/// Everything that can go wrong while talking to an upstream API.
+///
+/// Cheap to clone so one failed request can be reported to every caller
+/// that was waiting on it.
+#[derive(Debug, Clone)]
+pub enum FetchError {
+    /// The request never produced a response (DNS, TLS, connection reset, ...).
+    Transport(Arc<reqwest::Error>),
+    /// The upstream answered with a non-success status code.
+    Status(StatusCode),
+    /// The body could not be decoded into the expected model.
+    Decode(Arc<serde_json::Error>),
+    /// The upstream asked us to slow down.
+    RateLimited { retry_after: Option<Duration> },
+    /// The requested resource does not exist upstream.
+    NotFound,
+    /// The call did not complete within its time budget.
+    Timeout,
+    /// The upstream's circuit breaker is open, so the call was not attempted.
+    CircuitOpen,
+    /// The JSON-RPC node answered with an error object.
+    Rpc(String),
+}
+
+impl FetchError {
+    /// True when the upstream told us the resource does not exist, as opposed
+    /// to the upstream itself being unreachable or misbehaving.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, FetchError::NotFound)
+    }
+
+    /// True for failures that may succeed if the same request is sent again.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            FetchError::Transport(_) | FetchError::RateLimited { .. } | FetchError::Timeout => true,
+            FetchError::Status(status) => status.is_server_error(),
+            FetchError::Decode(_)
+            | FetchError::NotFound
+            | FetchError::CircuitOpen
+            | FetchError::Rpc(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Transport(e) => write!(f, "transport error: {e}"),
+            FetchError::Status(status) => write!(f, "unexpected status: {status}"),
+            FetchError::Decode(e) => write!(f, "decode error: {e}"),
+            FetchError::RateLimited {
+                retry_after: Some(after),
+            } => write!(f, "rate limited, retry after {}s", after.as_secs()),
+            FetchError::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            FetchError::NotFound => write!(f, "not found"),
+            FetchError::Timeout => write!(f, "timed out"),
+            FetchError::CircuitOpen => write!(f, "circuit open"),
+            FetchError::Rpc(message) => write!(f, "rpc error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FetchError::Transport(e) => Some(e.as_ref()),
+            FetchError::Decode(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(e: reqwest::Error) -> Self {
+        FetchError::Transport(Arc::new(e))
+    }
+}
+
+impl From<serde_json::Error> for FetchError {
+    fn from(e: serde_json::Error) -> Self {
+        FetchError::Decode(Arc::new(e))
+    }
+}
+
+/// Sends `request` and decodes the JSON body into `T`, mapping status codes
+/// onto [`FetchError`] variants.
+async fn fetch_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, FetchError> {
+    let response = request.send().await?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND => return Err(FetchError::NotFound),
+        StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return Err(FetchError::RateLimited { retry_after });
+        }
+        status if !status.is_success() => return Err(FetchError::Status(status)),
+        _ => {}
+    }
+
+    let text = response.text().await?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Client for the ape.express public API. Clones share one circuit breaker.
+#[derive(Debug, Clone)]
+pub struct ApeExpressClient {
+    client: Client,
+    base_url: String,
+    retry: RetryConfig,
+    circuit_breaker: Arc<CircuitBreaker>,
+}
+
+impl ApeExpressClient {
+    pub fn new(client: Client, config: &Config) -> Self {
+        Self {
+            client,
+            base_url: config.ape_express_api_url.clone(),
+            retry: config.retry.clone(),
+            circuit_breaker: Arc::new(CircuitBreaker::new(
+                "ape.express",
+                config.circuit_breaker.clone(),
+            )),
+        }
+    }
+
+    pub async fn token_info(&self, token_address: &Address) -> Result<TokenInfo, FetchError> {
+        let url = format!("{}/tokens/{}", self.base_url, token_address);
+        self.get(&url).await
+    }
+
+    pub async fn holders(&self, token_address: &Address) -> Result<TokenTopHolders, FetchError> {
+        let url = format!("{}/tokens/{}/holders", self.base_url, token_address);
+        self.get(&url).await
+    }
+
+    /// The ape.express profile and token holdings of a wallet. Fails with
+    /// [`FetchError::NotFound`] for wallets ape.express has never seen.
+    pub async fn wallet_holdings(&self, address: &Address) -> Result<WalletHoldings, FetchError> {
+        let url = format!("{}/users/{}", self.base_url, address);
+        self.get(&url).await
+    }
+
+    pub async fn native_token_price(&self) -> Result<NativeToken, FetchError> {
+        let url = format!("{}/tokens/ape", self.base_url);
+        self.get(&url).await
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T, FetchError> {
+        with_retry(&self.retry, || {
+            self.circuit_breaker.call(fetch_json(self.client.get(url)))
+        })
+        .await
+    }
+}
+
+/// Client for the DexTools public API. Clones share one circuit breaker and
+/// one rate limiter, sized to the configured API plan.
+#[derive(Debug, Clone)]
+pub struct DexToolsClient {
+    client: Client,
+    base_url: String,
+    api_key: String,
+    api_plan: String,
+    chain: String,
+    retry: RetryConfig,
+    rate_limiter: Arc<RateLimiter>,
+    circuit_breaker: Arc<CircuitBreaker>,
+}
+
+impl DexToolsClient {
+    pub fn new(client: Client, config: &Config) -> Self {
+        Self {
+            client,
+            base_url: config.dextools_api_url.clone(),
+            api_key: config.dextools_api_key.clone(),
+            api_plan: config.dextools_api_plan.clone(),
+            chain: config.dextools_chain.clone(),
+            retry: config.retry.clone(),
+            rate_limiter: Arc::new(RateLimiter::new(
+                config.dextools_rate_limit,
+                config.dextools_rate_limit.max(1.0),
+            )),
+            circuit_breaker: Arc::new(CircuitBreaker::new(
+                "DexTools",
+                config.circuit_breaker.clone(),
+            )),
+        }
+    }
+
+    pub async fn price_history(
+        &self,
+        token_address: &Address,
+    ) -> Result<TokenPriceHistory, FetchError> {
+        let url = format!(
+            "{}/{}/v2/token/{}/{}/price",
+            self.base_url, self.api_plan, self.chain, token_address
+        );
+        self.get(&url).await
+    }
+
+    pub async fn token_overview(
+        &self,
+        token_address: &Address,
+    ) -> Result<TokenOverview, FetchError> {
+        let url = format!(
+            "{}/{}/v2/token/{}/{}",
+            self.base_url, self.api_plan, self.chain, token_address
+        );
+        self.get(&url).await
+    }
+
+    pub async fn audit(&self, token_address: &Address) -> Result<TokenAudit, FetchError> {
+        let url = format!(
+            "{}/{}/v2/token/{}/{}/audit",
+            self.base_url, self.api_plan, self.chain, token_address
+        );
+        self.get(&url).await
+    }
+
+    /// The DEX pool at `pool_address`. Fails with [`FetchError::NotFound`]
+    /// when the address is not a pool DexTools knows.
+    pub async fn pool(&self, pool_address: &Address) -> Result<PoolInfo, FetchError> {
+        let url = format!(
+            "{}/{}/v2/pool/{}/{}",
+            self.base_url, self.api_plan, self.chain, pool_address
+        );
+        self.get(&url).await
+    }
+
+    /// Sends an authenticated GET, waiting for a rate limit token before
+    /// every attempt. The wait happens outside the circuit breaker, so time
+    /// queued behind our own limiter never counts against DexTools.
+    async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T, FetchError> {
+        with_retry(&self.retry, || async {
+            self.rate_limiter.acquire().await;
+            self.circuit_breaker
+                .call(fetch_json(
+                    self.client.get(url).header("X-API-KEY", &self.api_key),
+                ))
+                .await
+        })
+        .await
+    }
+}
+
+/// Client for an ApeChain JSON-RPC node. Clones share one circuit breaker.
+#[derive(Debug, Clone)]
+pub struct RpcClient {
+    client: Client,
+    url: String,
+    retry: RetryConfig,
+    circuit_breaker: Arc<CircuitBreaker>,
+}
+
+impl RpcClient {
+    pub fn new(client: Client, config: &Config) -> Self {
+        Self {
+            client,
+            url: config.apechain_rpc_url.clone(),
+            retry: config.retry.clone(),
+            circuit_breaker: Arc::new(CircuitBreaker::new(
+                "ApeChain RPC",
+                config.circuit_breaker.clone(),
+            )),
+        }
+    }
+
+    /// The deployed bytecode at `address`; `0x` for wallets.
+    pub async fn code(&self, address: &Address) -> Result<String, FetchError> {
+        self.call("eth_getCode", json!([address.to_string(), "latest"]))
+            .await?
+            .ok_or(FetchError::NotFound)
+    }
+
+    /// The native balance of `address`, in wei.
+    pub async fn balance(&self, address: &Address) -> Result<TokenAmount, FetchError> {
+        self.call("eth_getBalance", json!([address.to_string(), "latest"]))
+            .await?
+            .ok_or(FetchError::NotFound)
+    }
+
+    /// How many transactions `address` has sent.
+    pub async fn transaction_count(&self, address: &Address) -> Result<u64, FetchError> {
+        let count: TokenAmount = self
+            .call(
+                "eth_getTransactionCount",
+                json!([address.to_string(), "latest"]),
+            )
+            .await?
+            .ok_or(FetchError::NotFound)?;
+        Ok(count.raw().low_u64())
+    }
+
+    pub async fn transaction(&self, hash: &TxHash) -> Result<RpcTransaction, FetchError> {
+        self.call("eth_getTransactionByHash", json!([hash.to_string()]))
+            .await?
+            .ok_or(FetchError::NotFound)
+    }
+
+    /// The receipt of a mined transaction; `None` while it is pending.
+    pub async fn receipt(&self, hash: &TxHash) -> Result<Option<RpcReceipt>, FetchError> {
+        self.call("eth_getTransactionReceipt", json!([hash.to_string()]))
+            .await
+    }
+
+    /// Sends one JSON-RPC request. A `null` result is `Ok(None)`.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<Option<T>, FetchError> {
+        let body =
+            json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string();
+        let response: RpcResponse<T> = with_retry(&self.retry, || {
+            self.circuit_breaker.call(fetch_json(
+                self.client
+                    .post(&self.url)
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body.clone()),
+            ))
+        })
+        .await?;
+        match response.error {
+            Some(error) => Err(FetchError::Rpc(error.message)),
+            None => Ok(response.result),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use serde::Serialize;
+
+use crate::address::Address;
+use crate::config::LinkConfig;
+use crate::metrics::OverviewMetrics;
+use crate::scan::{AddressKind, NotScanned, ScanData};
+
+/// Most tokens compared in one reply; the rest are only counted.
+pub const MAX_COMPARED_TOKENS: usize = 10;
+
+/// A side-by-side summary of several tokens, as posted when one message
+/// contains more than one address.
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenComparison {
+    pub rows: Vec<ComparisonRow>,
+    /// Addresses that are not tokens or whose scan failed, with a short
+    /// reason.
+    pub failed: Vec<FailedScan>,
+    /// How many addresses were asked about, including any beyond
+    /// [`MAX_COMPARED_TOKENS`].
+    pub requested: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonRow {
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+    pub price_usd: Option<f64>,
+    pub market_cap_usd: Option<f64>,
+    pub liquidity_usd: Option<f64>,
+    pub holders: Option<u32>,
+    /// Price change over the last 24 hours, in percent.
+    pub variation_24h: Option<f64>,
+    pub dexscreener: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedScan {
+    pub address: String,
+    pub reason: &'static str,
+}
+
+impl TokenComparison {
+    /// How many tokens the comparison covers, scanned or failed.
+    pub fn shown(&self) -> usize {
+        self.rows.len() + self.failed.len()
+    }
+}
+
+/// Builds the comparison for `token_addresses`, all the addresses that
+/// were asked about, from the scans of the first [`MAX_COMPARED_TOKENS`]
+/// of them in the same order.
+pub fn compare(
+    token_addresses: &[Address],
+    scans: Vec<Result<ScanData, NotScanned>>,
+    links: &LinkConfig,
+) -> TokenComparison {
+    let mut rows = Vec::new();
+    let mut failed = Vec::new();
+    for (address, scan) in token_addresses.iter().zip(scans) {
+        match scan {
+            Ok(scan) => {
+                let metrics = OverviewMetrics::from_scan(&scan);
+                rows.push(ComparisonRow {
+                    dexscreener: links.dexscreener(&metrics.address),
+                    variation_24h: metrics
+                        .price_history
+                        .as_ref()
+                        .and_then(|history| history.variation_24h),
+                    holders: metrics.holders.as_ref().map(|holders| holders.total),
+                    address: metrics.address,
+                    name: metrics.name,
+                    symbol: metrics.symbol,
+                    price_usd: metrics.price_usd,
+                    market_cap_usd: metrics.market_cap_usd,
+                    liquidity_usd: metrics.liquidity_usd,
+                });
+            }
+            Err(e) => failed.push(FailedScan {
+                address: address.checksummed(),
+                reason: match e {
+                    NotScanned::NotToken(AddressKind::Wallet) => "wallet, not a token",
+                    NotScanned::NotToken(AddressKind::Pair { .. }) => {
+                        "liquidity pair, send it alone to see its token"
+                    }
+                    NotScanned::NotToken(_) => "not an ape.express token",
+                    NotScanned::Fetch(e) if e.is_not_found() => "not found",
+                    NotScanned::Fetch(_) => "unavailable",
+                },
+            }),
+        }
+    }
+    TokenComparison {
+        rows,
+        failed,
+        requested: token_addresses.len(),
+    }
+}
@@ -0,0 +1,306 @@
+use std::env;
+use std::fmt;
+use std::time::Duration;
+
+/// Runtime configuration, read from the environment (and `.env` via dotenv).
+///
+/// Every upstream base URL and outbound link can be overridden so the bot can
+/// run against local stand-ins or under a different explorer.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub dextools_api_key: String,
+    pub dextools_api_plan: String,
+    /// DexTools chain slug used in API paths, e.g. `apechain`.
+    pub dextools_chain: String,
+    /// Base URL of the ape.express API, without a trailing slash.
+    pub ape_express_api_url: String,
+    /// Base URL of the DexTools public API, without a trailing slash.
+    pub dextools_api_url: String,
+    /// Requests per second allowed by the DexTools plan.
+    pub dextools_rate_limit: f64,
+    /// ApeChain JSON-RPC endpoint, used to classify addresses and look up
+    /// wallets and transactions.
+    pub apechain_rpc_url: String,
+    pub links: LinkConfig,
+    pub timeouts: Timeouts,
+    pub cache_ttls: CacheTtls,
+    pub native_price: NativePriceConfig,
+    pub retry: RetryConfig,
+    pub circuit_breaker: CircuitBreakerConfig,
+    pub live_pin: LivePinConfig,
+}
+
+/// Outbound link templates. `{address}` is replaced with the token or wallet
+/// address, or the transaction hash, when rendering.
+#[derive(Debug, Clone)]
+pub struct LinkConfig {
+    pub dexscreener: String,
+    pub ape_express: String,
+    pub explorer_address: String,
+    pub explorer_token: String,
+    pub explorer_tx: String,
+    pub twitter_search: String,
+}
+
+/// Per-source upper bound on how long a scan waits for each upstream call.
+#[derive(Debug, Clone)]
+pub struct Timeouts {
+    pub token_info: Duration,
+    pub token_overview: Duration,
+    pub price_history: Duration,
+    pub holders: Duration,
+    pub audit: Duration,
+    pub wallet_holdings: Duration,
+    /// Each ApeChain JSON-RPC call.
+    pub rpc: Duration,
+}
+
+/// How long a successful response from each upstream is reused.
+#[derive(Debug, Clone)]
+pub struct CacheTtls {
+    pub token_info: Duration,
+    pub token_overview: Duration,
+    pub price_history: Duration,
+    pub holders: Duration,
+    pub audit: Duration,
+    pub wallet_holdings: Duration,
+}
+
+/// Settings for the background native APE price refresher.
+#[derive(Debug, Clone)]
+pub struct NativePriceConfig {
+    pub refresh_interval: Duration,
+    pub timeout: Duration,
+    /// Prices older than this are shown with a staleness notice.
+    pub max_age: Duration,
+}
+
+/// Retry policy for transient upstream failures.
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Total attempts including the first one.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+/// When a per-upstream circuit breaker opens and how long it stays open.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive transient failures that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe call is allowed.
+    pub cooldown: Duration,
+    /// How long a single attempt may take before it fails with
+    /// [`FetchError::Timeout`](crate::client::FetchError::Timeout) and counts
+    /// as a transient failure. Kept below the per-source timeouts so a hung
+    /// upstream is recorded before the caller gives up on it.
+    pub attempt_timeout: Duration,
+}
+
+/// Settings for the live overviews posted by `/pin`.
+#[derive(Debug, Clone)]
+pub struct LivePinConfig {
+    /// Time between edits. Never below [`LivePinConfig::MIN_REFRESH_INTERVAL`].
+    pub refresh_interval: Duration,
+    /// How long a live overview keeps updating after it is posted.
+    pub lifetime: Duration,
+}
+
+#[derive(Debug)]
+pub struct ConfigError {
+    pub var: &'static str,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} not set", self.var)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let dextools_api_plan = required("DEXTOOLS_API_PLAN")?;
+        Ok(Self {
+            dextools_api_key: required("DEXTOOLS_API_KEY")?,
+            dextools_rate_limit: env::var("DEXTOOLS_RATE_LIMIT")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .filter(|rate: &f64| *rate > 0.0)
+                .unwrap_or_else(|| plan_rate_limit(&dextools_api_plan)),
+            dextools_api_plan,
+            dextools_chain: optional("DEXTOOLS_CHAIN", "apechain"),
+            ape_express_api_url: base_url("APE_EXPRESS_API_URL", "https://ape.express/api"),
+            dextools_api_url: base_url("DEXTOOLS_API_URL", "https://public-api.dextools.io"),
+            apechain_rpc_url: optional("APECHAIN_RPC_URL", "https://rpc.apechain.com/http"),
+            links: LinkConfig::from_env(),
+            timeouts: Timeouts::from_env(),
+            cache_ttls: CacheTtls::from_env(),
+            native_price: NativePriceConfig::from_env(),
+            retry: RetryConfig::from_env(),
+            circuit_breaker: CircuitBreakerConfig::from_env(),
+            live_pin: LivePinConfig::from_env(),
+        })
+    }
+}
+
+impl LinkConfig {
+    pub fn from_env() -> Self {
+        Self {
+            dexscreener: optional(
+                "DEXSCREENER_LINK",
+                "https://dexscreener.com/apechain/{address}",
+            ),
+            ape_express: optional("APE_EXPRESS_LINK", "https://ape.express/explore/{address}?"),
+            explorer_address: optional(
+                "EXPLORER_ADDRESS_LINK",
+                "https://apescan.io/address/{address}",
+            ),
+            explorer_token: optional("EXPLORER_TOKEN_LINK", "https://apescan.io/token/{address}"),
+            explorer_tx: optional("EXPLORER_TX_LINK", "https://apescan.io/tx/{address}"),
+            twitter_search: optional(
+                "TWITTER_SEARCH_LINK",
+                "https://twitter.com/search?q={address}=typed_query&f=live",
+            ),
+        }
+    }
+
+    pub fn dexscreener(&self, address: &str) -> String {
+        render(&self.dexscreener, address)
+    }
+
+    pub fn ape_express(&self, address: &str) -> String {
+        render(&self.ape_express, address)
+    }
+
+    pub fn explorer_address(&self, address: &str) -> String {
+        render(&self.explorer_address, address)
+    }
+
+    pub fn explorer_token(&self, address: &str) -> String {
+        render(&self.explorer_token, address)
+    }
+
+    pub fn explorer_tx(&self, hash: &str) -> String {
+        render(&self.explorer_tx, hash)
+    }
+
+    pub fn twitter_search(&self, address: &str) -> String {
+        render(&self.twitter_search, address)
+    }
+}
+
+impl Timeouts {
+    pub fn from_env() -> Self {
+        Self {
+            token_info: millis("TOKEN_INFO_TIMEOUT_MS", 5_000),
+            token_overview: millis("TOKEN_OVERVIEW_TIMEOUT_MS", 4_000),
+            price_history: millis("PRICE_HISTORY_TIMEOUT_MS", 4_000),
+            holders: millis("HOLDERS_TIMEOUT_MS", 4_000),
+            audit: millis("AUDIT_TIMEOUT_MS", 4_000),
+            wallet_holdings: millis("WALLET_HOLDINGS_TIMEOUT_MS", 5_000),
+            rpc: millis("RPC_TIMEOUT_MS", 4_000),
+        }
+    }
+}
+
+impl CacheTtls {
+    pub fn from_env() -> Self {
+        Self {
+            token_info: secs("TOKEN_INFO_CACHE_TTL_SECS", 30),
+            token_overview: secs("TOKEN_OVERVIEW_CACHE_TTL_SECS", 3_600),
+            price_history: secs("PRICE_HISTORY_CACHE_TTL_SECS", 60),
+            holders: secs("HOLDERS_CACHE_TTL_SECS", 60),
+            audit: secs("AUDIT_CACHE_TTL_SECS", 3_600),
+            wallet_holdings: secs("WALLET_HOLDINGS_CACHE_TTL_SECS", 60),
+        }
+    }
+}
+
+impl NativePriceConfig {
+    pub fn from_env() -> Self {
+        Self {
+            refresh_interval: secs("NATIVE_PRICE_REFRESH_SECS", 30),
+            timeout: millis("NATIVE_PRICE_TIMEOUT_MS", 3_000),
+            max_age: secs("NATIVE_PRICE_MAX_AGE_SECS", 300),
+        }
+    }
+}
+
+impl RetryConfig {
+    pub fn from_env() -> Self {
+        Self {
+            max_attempts: number("RETRY_MAX_ATTEMPTS", 3).max(1) as u32,
+            base_delay: millis("RETRY_BASE_DELAY_MS", 200),
+            max_delay: millis("RETRY_MAX_DELAY_MS", 2_000),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    pub fn from_env() -> Self {
+        Self {
+            failure_threshold: number("CIRCUIT_BREAKER_FAILURES", 5).max(1) as u32,
+            cooldown: secs("CIRCUIT_BREAKER_COOLDOWN_SECS", 30),
+            attempt_timeout: millis("CIRCUIT_BREAKER_ATTEMPT_TIMEOUT_MS", 2_500),
+        }
+    }
+}
+
+impl LivePinConfig {
+    /// Telegram throttles bots that edit messages in a group more than about
+    /// once every few seconds; live overviews stay well clear of that.
+    pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+    pub fn from_env() -> Self {
+        Self {
+            refresh_interval: secs("PIN_REFRESH_INTERVAL_SECS", 120)
+                .max(Self::MIN_REFRESH_INTERVAL),
+            lifetime: secs("PIN_LIFETIME_SECS", 86_400),
+        }
+    }
+}
+
+/// Requests per second allowed by each DexTools API plan. Unknown plans get
+/// the free tier's limit.
+fn plan_rate_limit(plan: &str) -> f64 {
+    match plan.to_lowercase().as_str() {
+        "standard" => 2.0,
+        "advanced" => 4.0,
+        "pro" => 10.0,
+        _ => 1.0,
+    }
+}
+
+fn render(template: &str, address: &str) -> String {
+    template.replace("{address}", address)
+}
+
+fn required(var: &'static str) -> Result<String, ConfigError> {
+    env::var(var).map_err(|_| ConfigError { var })
+}
+
+fn optional(var: &str, default: &str) -> String {
+    env::var(var).unwrap_or_else(|_| default.to_string())
+}
+
+fn base_url(var: &str, default: &str) -> String {
+    optional(var, default).trim_end_matches('/').to_string()
+}
+
+fn number(var: &str, default: u64) -> u64 {
+    env::var(var)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+fn millis(var: &str, default: u64) -> Duration {
+    Duration::from_millis(number(var, default))
+}
+
+fn secs(var: &str, default: u64) -> Duration {
+    Duration::from_secs(number(var, default))
+}
@@ -0,0 +1,139 @@
+/// Addresses are `0x` followed by this many hex digits.
+const ADDRESS_HEX_LEN: usize = 40;
+
+/// Transaction hashes are `0x` followed by this many hex digits.
+const TX_HASH_HEX_LEN: usize = 64;
+
+/// Finds every address in free text: pasted on its own, mid-sentence, or
+/// inside a link such as a DEX Screener, ape.express or apescan URL.
+/// Longer hex strings like transaction hashes are not mistaken for
+/// addresses. Duplicates (in any letter case) are dropped, keeping the
+/// order of first appearance.
+pub fn extract_addresses(text: &str) -> Vec<String> {
+    extract_hex(text, ADDRESS_HEX_LEN)
+}
+
+/// Finds every transaction hash in free text, the same way
+/// [`extract_addresses`] finds addresses, e.g. inside an apescan `/tx/` link.
+pub fn extract_tx_hashes(text: &str) -> Vec<String> {
+    extract_hex(text, TX_HASH_HEX_LEN)
+}
+
+/// Every distinct `0x` string of exactly `hex_len` hex digits in `text`.
+fn extract_hex(text: &str, hex_len: usize) -> Vec<String> {
+    let bytes = text.as_bytes();
+    let mut found: Vec<String> = Vec::new();
+    let mut index = 0;
+    while index + 2 + hex_len <= bytes.len() {
+        if let Some(hex) = hex_at(bytes, index, hex_len) {
+            if !found.iter().any(|seen| seen.eq_ignore_ascii_case(hex)) {
+                found.push(hex.to_string());
+            }
+            index += hex.len();
+        } else {
+            index += 1;
+        }
+    }
+    found
+}
+
+/// The `0x` string of `hex_len` digits starting at `start`, if there is one
+/// delimited on both sides by something other than a letter or digit.
+fn hex_at(bytes: &[u8], start: usize, hex_len: usize) -> Option<&str> {
+    let end = start + 2 + hex_len;
+    let candidate = bytes.get(start..end)?;
+    let delimited_before = start == 0 || !bytes[start - 1].is_ascii_alphanumeric();
+    let delimited_after = bytes.get(end).is_none_or(|c| !c.is_ascii_alphanumeric());
+    let shaped = candidate[0] == b'0'
+        && (candidate[1] == b'x' || candidate[1] == b'X')
+        && candidate[2..].iter().all(u8::is_ascii_hexdigit);
+    if delimited_before && delimited_after && shaped {
+        std::str::from_utf8(candidate).ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    const OTHER: &str = "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359";
+    const TX_HASH: &str = "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b";
+
+    #[test]
+    fn finds_a_bare_address() {
+        assert_eq!(extract_addresses(ADDRESS), vec![ADDRESS]);
+    }
+
+    #[test]
+    fn finds_an_address_inside_a_url() {
+        let text = format!("chart: https://dexscreener.com/apechain/{ADDRESS}?maker=1");
+        assert_eq!(extract_addresses(&text), vec![ADDRESS]);
+        let text = format!("https://apescan.io/token/{ADDRESS}#balances");
+        assert_eq!(extract_addresses(&text), vec![ADDRESS]);
+    }
+
+    #[test]
+    fn finds_addresses_in_caption_text() {
+        let text = format!("New launch 🚀\nCA: {ADDRESS}\nalso watching {OTHER}, thoughts?");
+        assert_eq!(extract_addresses(&text), vec![ADDRESS, OTHER]);
+    }
+
+    #[test]
+    fn allows_punctuation_around_an_address() {
+        for text in [
+            format!("({ADDRESS})"),
+            format!("\"{ADDRESS}\"."),
+            format!("ca:{ADDRESS},"),
+            format!("<{ADDRESS}>"),
+        ] {
+            assert_eq!(extract_addresses(&text), vec![ADDRESS], "{text}");
+        }
+    }
+
+    #[test]
+    fn tx_hash_is_not_an_address() {
+        let text = format!("tx {TX_HASH} done");
+        assert!(extract_addresses(&text).is_empty());
+        assert_eq!(extract_tx_hashes(&text), vec![TX_HASH]);
+        let link = format!("https://apescan.io/tx/{TX_HASH}");
+        assert!(extract_addresses(&link).is_empty());
+        assert_eq!(extract_tx_hashes(&link), vec![TX_HASH]);
+    }
+
+    #[test]
+    fn address_is_not_a_tx_hash() {
+        assert!(extract_tx_hashes(ADDRESS).is_empty());
+    }
+
+    #[test]
+    fn ignores_over_long_and_glued_hex_runs() {
+        for text in [
+            format!("{ADDRESS}0"),
+            format!("{ADDRESS}g"),
+            format!("a{ADDRESS}"),
+            format!("0{ADDRESS}"),
+            format!("{ADDRESS}{}", &OTHER[2..]),
+        ] {
+            assert!(extract_addresses(&text).is_empty(), "{text}");
+        }
+    }
+
+    #[test]
+    fn dedups_case_insensitively_keeping_first_seen_order() {
+        let text = format!(
+            "{OTHER} {ADDRESS} {} {}",
+            ADDRESS.to_lowercase(),
+            OTHER.to_uppercase().replacen("0X", "0x", 1)
+        );
+        assert_eq!(extract_addresses(&text), vec![OTHER, ADDRESS]);
+    }
+
+    #[test]
+    fn accepts_an_uppercase_prefix() {
+        let text = format!("0X{}", &OTHER[2..]);
+        assert_eq!(extract_addresses(&text), vec![text.as_str()]);
+    }
+}
@@ -0,0 +1,297 @@
+use reqwest::Url;
+
+/// Escapes text for Telegram's HTML parse mode, in element content as well
+/// as in quoted attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns `url` normalized if it is an absolute http(s) URL, and `None` for
+/// anything else (other schemes, relative paths, garbage).
+pub fn safe_url(url: &str) -> Option<String> {
+    let parsed = Url::parse(url.trim()).ok()?;
+    match parsed.scheme() {
+        "http" | "https" if parsed.host_str().is_some() => Some(parsed.to_string()),
+        _ => None,
+    }
+}
+
+/// Length of `text` the way Telegram counts it, in UTF-16 code units.
+/// Applied to raw HTML this over-counts (tags and link targets are not
+/// visible), so anything that fits by this measure fits in Telegram too.
+pub fn telegram_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+/// Splits an HTML message into ordered parts of at most `limit` code units
+/// (see [`telegram_len`]). Parts end after a newline where possible and never
+/// inside a tag or an entity. An element too long for one part is closed at
+/// the cut and reopened at the start of the next; if even that cannot fit
+/// (say, a link whose target alone is over the limit), the rest of the
+/// message is sent without tags.
+pub fn split(html: &str, limit: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut rest = html.to_string();
+    let mut reopened = 0;
+    while telegram_len(&rest) > limit {
+        let Some(cut) = split_point(&rest, limit, reopened) else {
+            rest = strip_tags(&rest);
+            reopened = 0;
+            continue;
+        };
+        let mut part = rest[..cut.offset].to_string();
+        let mut reopen = String::new();
+        for tag in cut.open.iter().rev() {
+            part += &format!("</{}>", tag.name);
+        }
+        for tag in &cut.open {
+            reopen += &tag.opening;
+        }
+        if has_text(&part) {
+            parts.push(part);
+        }
+        reopened = reopen.len();
+        rest = reopen + &rest[cut.offset..];
+    }
+    if has_text(&rest) {
+        parts.push(rest);
+    }
+    parts
+}
+
+/// Whether `html` shows anything but whitespace. Telegram rejects empty
+/// messages, including ones that are only tags.
+fn has_text(html: &str) -> bool {
+    !strip_tags(html).trim().is_empty()
+}
+
+/// Where to end the first part of an HTML message, and the elements still
+/// open there.
+struct Cut {
+    offset: usize,
+    open: Vec<OpenTag>,
+}
+
+#[derive(Clone)]
+struct OpenTag {
+    name: String,
+    /// The opening tag as written, attributes included.
+    opening: String,
+}
+
+/// Where to end the first part of `html`, past its first `min_offset`
+/// bytes: the last newline outside any element that fits, else the last
+/// character outside any element that fits, else the last character outside
+/// a tag or entity that fits together with the closing tags it needs.
+/// `None` if there is no such point.
+fn split_point(html: &str, limit: usize, min_offset: usize) -> Option<Cut> {
+    let mut units = 0;
+    let mut open: Vec<OpenTag> = Vec::new();
+    let mut closing_units = 0;
+    let mut tag_start = None;
+    let mut in_entity = false;
+    let mut last_newline = None;
+    let mut last_safe = None;
+    let mut last_nested = None;
+
+    for (index, c) in html.char_indices() {
+        units += c.len_utf16();
+        if units > limit {
+            break;
+        }
+        let end = index + c.len_utf8();
+        match c {
+            '<' if tag_start.is_none() => tag_start = Some(index),
+            '>' if tag_start.is_some() => {
+                let tag = &html[tag_start.take().unwrap_or_default()..end];
+                if tag.starts_with("</") {
+                    if let Some(closed) = open.pop() {
+                        closing_units -= closed.name.len() + 3;
+                    }
+                } else {
+                    let name: String = tag[1..]
+                        .chars()
+                        .take_while(char::is_ascii_alphanumeric)
+                        .collect();
+                    closing_units += name.len() + 3;
+                    open.push(OpenTag {
+                        name,
+                        opening: tag.to_string(),
+                    });
+                }
+            }
+            '&' if tag_start.is_none() => in_entity = true,
+            ';' if in_entity => in_entity = false,
+            _ => {}
+        }
+
+        if tag_start.is_some() || in_entity || end <= min_offset {
+            continue;
+        }
+        if open.is_empty() {
+            if c == '\n' {
+                last_newline = Some(end);
+            }
+            last_safe = Some(end);
+        } else if units + closing_units <= limit {
+            last_nested = Some(Cut {
+                offset: end,
+                open: open.clone(),
+            });
+        }
+    }
+
+    match last_newline.or(last_safe) {
+        Some(offset) => Some(Cut {
+            offset,
+            open: Vec::new(),
+        }),
+        None => last_nested,
+    }
+}
+
+/// `html` without its tags. Entities are kept, so the result is still valid
+/// Telegram HTML.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Strips the tags from Telegram HTML and decodes the entities [`escape`]
+/// produces, for sending the same text without a parse mode.
+pub fn to_plain_text(html: &str) -> String {
+    strip_tags(html)
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every part fits and has balanced tags.
+    fn assert_well_formed(parts: &[String], limit: usize) {
+        for part in parts {
+            assert!(telegram_len(part) <= limit, "part too long: {part:?}");
+            assert_eq!(
+                part.matches("<b>").count() + part.matches("<a ").count(),
+                part.matches("</b>").count() + part.matches("</a>").count(),
+                "unbalanced part: {part:?}"
+            );
+            assert_eq!(
+                part.matches('&').count(),
+                part.matches(';').count(),
+                "cut entity: {part:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn telegram_len_counts_utf16_units() {
+        assert_eq!(telegram_len("abc"), 3);
+        assert_eq!(telegram_len("é"), 1);
+        assert_eq!(telegram_len("🚀"), 2);
+        assert_eq!(telegram_len("👩‍👧‍👦"), 8);
+    }
+
+    #[test]
+    fn short_message_is_one_part() {
+        assert_eq!(split("<b>hi</b>\n", 100), vec!["<b>hi</b>\n"]);
+        assert!(split("  \n", 100).is_empty());
+    }
+
+    #[test]
+    fn splits_after_newlines_between_elements() {
+        let html = "<b>one</b>\n<b>two</b>\n<b>three</b>\n";
+        let parts = split(html, 24);
+        assert_eq!(parts, vec!["<b>one</b>\n<b>two</b>\n", "<b>three</b>\n"]);
+        assert_eq!(parts.concat(), html);
+    }
+
+    #[test]
+    fn never_cuts_inside_a_tag_or_entity() {
+        let html = "a &amp; b <b>bold</b> c &lt; d";
+        for limit in 12..html.len() {
+            assert_well_formed(&split(html, limit), limit);
+        }
+    }
+
+    #[test]
+    fn long_element_is_closed_and_reopened() {
+        let html = format!("<b>{}</b>", "x".repeat(50));
+        let parts = split(&html, 20);
+        assert_well_formed(&parts, 20);
+        assert!(parts.len() > 1);
+        assert!(parts.iter().all(|part| part.starts_with("<b>")));
+        assert_eq!(to_plain_text(&parts.concat()), "x".repeat(50));
+    }
+
+    #[test]
+    fn nested_elements_are_reopened_in_order() {
+        let html = format!("<b><a href=\"https://x.io\">{}</a></b>", "y".repeat(60));
+        let parts = split(&html, 50);
+        assert_well_formed(&parts, 50);
+        assert!(parts[1].starts_with("<b><a href=\"https://x.io\">"));
+        assert!(parts[0].ends_with("</a></b>"));
+        assert_eq!(to_plain_text(&parts.concat()), "y".repeat(60));
+    }
+
+    #[test]
+    fn long_anchor_keeps_its_link() {
+        let html = format!(
+            "<a href=\"https://example.com/0xabc\">{}</a>\n",
+            "link text ".repeat(20)
+        );
+        let parts = split(&html, 80);
+        assert_well_formed(&parts, 80);
+        assert!(parts
+            .iter()
+            .all(|part| part.starts_with("<a href=\"https://example.com/0xabc\">")));
+        assert_eq!(
+            to_plain_text(&parts.concat()).trim_end(),
+            to_plain_text(&html).trim_end()
+        );
+    }
+
+    #[test]
+    fn anchor_longer_than_the_limit_loses_its_tags() {
+        let html = format!(
+            "<a href=\"https://example.com/{}\">text</a> and more",
+            "a".repeat(100)
+        );
+        let parts = split(&html, 40);
+        assert_well_formed(&parts, 40);
+        assert!(!parts.concat().contains('<'));
+        assert_eq!(parts.concat(), "text and more");
+    }
+
+    #[test]
+    fn counts_emoji_as_two_units() {
+        let html = "🚀".repeat(30);
+        let parts = split(&html, 11);
+        assert_well_formed(&parts, 11);
+        assert!(parts.iter().all(|part| telegram_len(part) == 10));
+        assert_eq!(parts.concat(), html);
+    }
+}
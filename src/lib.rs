@@ -0,0 +1,38 @@
+//! Token scanner for ape.express tokens on ApeChain.
+//!
+//! Fetches token info, holders and price history from ape.express and
+//! DexTools and renders them as an overview. Wallets and transactions pasted
+//! instead of a token are summarized from an ApeChain RPC node. The Telegram
+//! bot in `main.rs` is one consumer of this library.
+
+pub mod action;
+pub mod address;
+pub mod cache;
+pub mod circuit_breaker;
+pub mod client;
+pub mod compare;
+pub mod config;
+pub mod extract;
+pub mod html;
+pub mod metrics;
+pub mod native_token;
+pub mod overview;
+pub mod pool_info;
+pub mod price_feed;
+pub mod rate_limit;
+pub mod render;
+pub mod report;
+pub mod retry;
+pub mod risk;
+pub mod rpc;
+pub mod scan;
+pub mod section;
+pub mod token_amount;
+pub mod token_audit;
+pub mod token_holders;
+pub mod token_info;
+pub mod token_overview;
+pub mod token_price_history;
+pub mod transaction;
+pub mod wallet;
+pub mod wallet_holdings;
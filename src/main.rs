@@ -1,25 +1,39 @@
-pub mod native_token;
-pub mod token_audit;
-pub mod token_holders;
-pub mod token_info;
-pub mod token_price_history;
-
-use chrono::{DateTime, Utc};
 use dotenv::dotenv;
-use log::error;
-use native_token::*;
-use reqwest::Client;
-use serde_json;
-use std::env;
-use teloxide::types::LinkPreviewOptions;
+use gorilla_scan::action::OverviewAction;
+use gorilla_scan::address::{Address, Checksum, TxHash};
+use gorilla_scan::client::*;
+use gorilla_scan::compare::{compare, MAX_COMPARED_TOKENS};
+use gorilla_scan::config::*;
+use gorilla_scan::extract::{extract_addresses, extract_tx_hashes};
+use gorilla_scan::html::{self, to_plain_text};
+use gorilla_scan::render::{
+    comparison_html, plain_text, price_in_ape_text, telegram_messages, telegram_single_message,
+    telegram_truncated_message, transaction_html, wallet_html, TELEGRAM_MESSAGE_LIMIT,
+};
+use gorilla_scan::report::{analyze, TokenOverview};
+use gorilla_scan::scan::*;
+use gorilla_scan::section::{section_html, section_messages, Section};
+use gorilla_scan::transaction::transaction_report;
+use gorilla_scan::wallet::wallet_report;
+use log::{error, info, warn};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, LinkPreviewOptions, MessageId};
 use teloxide::{
     prelude::*,
     types::{Me, MessageKind, ParseMode},
     utils::command::BotCommands,
+    ApiError, RequestError,
 };
-use token_holders::*;
-use token_info::*;
-use token_price_history::*;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// Most transactions summarized in reply to one message.
+const MAX_TRANSACTIONS: usize = 3;
+
+/// The task keeping each chat's live `/pin` overview current, with the
+/// message it edits.
+type LivePins = Arc<Mutex<HashMap<ChatId, (MessageId, JoinHandle<()>)>>>;
 
 #[derive(BotCommands, Clone)]
 #[command(
@@ -31,6 +45,24 @@ enum Command {
     Help,
     #[command(description = "Send the welcome message")]
     Start,
+    #[command(
+        description = "Show the overview of a token, wallet or transaction: /scan <address or tx hash>"
+    )]
+    Scan(String),
+    #[command(description = "Show the price and price history: /price <address>")]
+    Price(String),
+    #[command(description = "Show the holder stats and top holders: /holders <address>")]
+    Holders(String),
+    #[command(description = "Show the security audit: /audit <address>")]
+    Audit(String),
+    #[command(description = "Show the liquidity pool: /liquidity <address>")]
+    Liquidity(String),
+    #[command(description = "Show the socials and links: /socials <address>")]
+    Socials(String),
+    #[command(description = "Show the risk score: /risk <address>")]
+    Risk(String),
+    #[command(description = "Post and pin a live, auto-updating overview: /pin <address>")]
+    Pin(String),
 }
 
 #[tokio::main]
@@ -40,6 +72,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     log::info!("Starting command bot...");
     let bot = Bot::from_env();
 
+    let config = Arc::new(Config::from_env()?);
+    let scanner = Arc::new(Scanner::from_config(&config));
+    scanner.spawn_native_price_refresher(&config.native_price);
+    let live_pins: LivePins = Arc::default();
+
     let bot_commands = Command::bot_commands();
     if bot.set_my_commands(bot_commands).await.is_err() {
         log::warn!("Could not set up the commands.");
@@ -47,8 +84,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Dispatcher::builder(
         bot,
-        dptree::entry().branch(Update::filter_message().endpoint(message_handler)),
+        dptree::entry()
+            .branch(Update::filter_message().endpoint(message_handler))
+            .branch(Update::filter_callback_query().endpoint(callback_handler)),
     )
+    .dependencies(dptree::deps![config, scanner, live_pins])
     .build()
     .dispatch()
     .await;
@@ -56,13 +96,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-async fn message_handler(bot: Bot, msg: Message, me: Me) -> ResponseResult<()> {
+async fn message_handler(
+    bot: Bot,
+    msg: Message,
+    me: Me,
+    config: Arc<Config>,
+    scanner: Arc<Scanner>,
+    live_pins: LivePins,
+) -> ResponseResult<()> {
     dotenv().ok();
 
     if let MessageKind::WebAppData(data) = msg.kind {
         bot.send_message(msg.chat.id, data.web_app_data.data)
             .await?;
-    } else if let Some(text) = msg.text() {
+    } else if let Some(text) = msg.text().or(msg.caption()) {
         let chat_type = match msg.chat.kind {
             teloxide::types::ChatKind::Private { .. } => "a private chat".to_string(),
             teloxide::types::ChatKind::Public(ref public_chat) => match public_chat.kind {
@@ -74,17 +121,19 @@ async fn message_handler(bot: Bot, msg: Message, me: Me) -> ResponseResult<()> {
 
         if chat_type == "a group" || chat_type == "a supergroup" {
             let username = msg
-                .from()
+                .from
+                .as_ref()
                 .and_then(|user| user.username.clone())
                 .unwrap_or_else(|| {
-                    msg.from()
+                    msg.from
+                        .as_ref()
                         .map(|user| user.first_name.clone())
                         .unwrap_or_else(|| "Unknown User".to_string())
                 });
             if let Ok(cmd) = Command::parse(text, me.username()) {
-                answer_command(bot, msg, cmd, username).await?;
+                answer_command(bot, msg, cmd, username, config, scanner, live_pins).await?;
             } else {
-                answer_message(bot, msg).await?;
+                answer_message(bot, msg, config, scanner).await?;
             }
         } else {
             bot.send_message(
@@ -103,439 +152,572 @@ async fn answer_command(
     msg: Message,
     cmd: Command,
     username: String,
+    config: Arc<Config>,
+    scanner: Arc<Scanner>,
+    live_pins: LivePins,
 ) -> ResponseResult<()> {
-    match cmd {
+    let (argument, section) = match cmd {
         Command::Help => {
             bot.send_message(msg.chat.id, Command::descriptions().to_string())
                 .await?;
+            return Ok(());
         }
         Command::Start => {
             bot.send_message(msg.chat.id, format!("Welcome to Here @{username}! 🎉"))
                 .await?;
+            return Ok(());
+        }
+        Command::Scan(argument) => {
+            if extract_addresses(&argument).is_empty() {
+                if let Some(hash) = tx_hashes_in(&argument).first() {
+                    return answer_transaction(&bot, msg.chat.id, &config, &scanner, hash).await;
+                }
+            }
+            (argument, None)
         }
-        _ => {
-            bot.send_message(msg.chat.id, "Invalid command").await?;
+        Command::Price(token_adr) => (token_adr, Some(Section::Price)),
+        Command::Holders(token_adr) => (token_adr, Some(Section::Holders)),
+        Command::Audit(token_adr) => (token_adr, Some(Section::Audit)),
+        Command::Liquidity(token_adr) => (token_adr, Some(Section::Liquidity)),
+        Command::Socials(token_adr) => (token_adr, Some(Section::Socials)),
+        Command::Risk(token_adr) => (token_adr, Some(Section::Risk)),
+        Command::Pin(token_adr) => {
+            let Some(token_adr) = command_address(&bot, msg.chat.id, &token_adr).await? else {
+                return Ok(());
+            };
+            return answer_pin(&bot, msg.chat.id, config, scanner, &live_pins, token_adr).await;
         }
-    }
-    Ok(())
+    };
+    let Some(address) = command_address(&bot, msg.chat.id, &argument).await? else {
+        return Ok(());
+    };
+    answer_address(&bot, msg.chat.id, &config, &scanner, &address, section).await
 }
 
-async fn answer_message(bot: Bot, msg: Message) -> ResponseResult<()> {
-    let token_adr = msg.text().unwrap();
-    if token_adr.starts_with("0x")
-        && token_adr.len() == 42
-        && token_adr[2..].chars().all(|c| c.is_ascii_hexdigit())
-    {
-        let request_client = Client::new();
-        let dextools_api_key = env::var("DEXTOOLS_API_KEY").expect("API_KEY not set");
-        let dextools_api_plan = env::var("DEXTOOLS_API_PLAN").expect("API_PLAN not set");
-
-        match get_token_info(request_client.clone(), &token_adr).await {
-            Ok(token_info) => {
-                let token_price_history = get_token_price_history(
-                    request_client.clone(),
-                    &dextools_api_key,
-                    &dextools_api_plan,
-                    &token_adr,
-                )
-                .await
-                .unwrap_or_default();
-                let token_holders = get_holders(request_client.clone(), &token_adr)
-                    .await
-                    .unwrap_or_default();
-                // let token_audit = get_token_audit(request_client.clone(), &dextools_api_key, &dextools_api_plan, &token_adr).await.unwrap_or_default();
-                //make message
-                let text =
-                    make_token_overview_message(&token_info, &token_price_history, &token_holders)
-                        .await?;
-                bot.send_message(msg.chat.id, text) // Changed "text" to text
-                    .parse_mode(ParseMode::Html)
-                    .link_preview_options(LinkPreviewOptions {
-                        is_disabled: true,
-                        prefer_small_media: false,
-                        prefer_large_media: false,
-                        show_above_text: false,
-                        url: None,
-                    })
-                    .send()
-                    .await?;
-            }
-            Err(e) => {
-                error!("Error fetching token overview: {}", e);
-                bot.send_message(msg.chat.id, "Invalid token address")
-                    .await?;
-            }
+async fn answer_message(
+    bot: Bot,
+    msg: Message,
+    config: Arc<Config>,
+    scanner: Arc<Scanner>,
+) -> ResponseResult<()> {
+    let text = msg.text().or(msg.caption()).unwrap_or_default();
+    match addresses_in(&bot, msg.chat.id, text).await?.as_slice() {
+        [] => {}
+        [address] => answer_address(&bot, msg.chat.id, &config, &scanner, address, None).await?,
+        token_addresses => {
+            answer_batch(&bot, msg.chat.id, &config, &scanner, token_addresses).await?
         }
     }
+    for hash in tx_hashes_in(text).iter().take(MAX_TRANSACTIONS) {
+        answer_transaction(&bot, msg.chat.id, &config, &scanner, hash).await?;
+    }
     Ok(())
 }
 
-async fn get_token_info(
-    client: Client,
-    token_address: &str,
-) -> Result<TokenInfo, serde_json::Error> {
-    let url = format!("https://ape.express/api/tokens/{}", token_address);
-
-    let response = client.get(&url).send().await.unwrap();
-
-    let text = match response.text().await {
-        Ok(text) => text,
-        Err(e) => {
-            println!("Error fetching token overview: {}", e);
-            // format!(e.to_string());
-            "Error fetching token overview: {e}".to_string()
+/// Replies according to what `address` turns out to be: the overview (or
+/// one `section` of it) of a token, or of the token a pool trades, and a
+/// summary of a wallet.
+async fn answer_address(
+    bot: &Bot,
+    chat_id: ChatId,
+    config: &Config,
+    scanner: &Scanner,
+    address: &Address,
+    section: Option<Section>,
+) -> ResponseResult<()> {
+    match scanner.classify(address).await {
+        Ok(AddressKind::Token) => {
+            answer_token(bot, chat_id, config, scanner, address, section).await
+        }
+        Ok(AddressKind::Pair { token }) => {
+            bot.send_message(
+                chat_id,
+                format!(
+                    "<code>{}</code> is a liquidity pair, showing its token <code>{}</code>.",
+                    address.checksummed(),
+                    token.checksummed()
+                ),
+            )
+            .parse_mode(ParseMode::Html)
+            .await?;
+            answer_token(bot, chat_id, config, scanner, &token, section).await
+        }
+        Ok(AddressKind::Wallet) => answer_wallet(bot, chat_id, config, scanner, address).await,
+        Ok(AddressKind::Contract) => {
+            bot.send_message(chat_id, "This contract is not an ape.express token.")
+                .await?;
+            Ok(())
         }
-    };
-    match serde_json::from_str(&text) {
-        Ok(obj) => Ok(obj),
         Err(e) => {
-            println!("Error parsing JSON: {}", e);
-            Err(e)
+            error!("Error classifying {}: {}", address, e);
+            bot.send_message(
+                chat_id,
+                "Could not look up this address right now, please try again later.",
+            )
+            .await?;
+            Ok(())
         }
     }
 }
 
-async fn get_token_price_history(
-    client: Client,
-    api_key: &str,
-    api_plan: &str,
-    token_address: &str,
-) -> Result<TokenPriceHistory, serde_json::Error> {
-    let url = format!(
-        "https://public-api.dextools.io/{}/v2/token/{}/{}/price",
-        api_plan, "apechain", token_address
-    );
-
-    let response = client
-        .get(&url)
-        .header("X-API-KEY", api_key)
-        .send()
-        .await
-        .unwrap();
+/// Replies with the summary of a wallet.
+async fn answer_wallet(
+    bot: &Bot,
+    chat_id: ChatId,
+    config: &Config,
+    scanner: &Scanner,
+    address: &Address,
+) -> ResponseResult<()> {
+    match scanner.wallet(address).await {
+        Ok(wallet) => {
+            let text = wallet_html(&wallet_report(&wallet, &config.links));
+            let parts = html::split(&text, TELEGRAM_MESSAGE_LIMIT);
+            reply_html(bot, chat_id, parts, to_plain_text(&text), None).await
+        }
+        Err(e) => {
+            error!("Error fetching wallet {}: {}", address, e);
+            bot.send_message(
+                chat_id,
+                "ApeChain is unavailable right now, please try again later.",
+            )
+            .await?;
+            Ok(())
+        }
+    }
+}
 
-    let text = response.text().await.unwrap();
-    match serde_json::from_str(&text) {
-        Ok(obj) => Ok(obj),
-        Err(e) => Err(e),
+/// Replies with the summary of a transaction.
+async fn answer_transaction(
+    bot: &Bot,
+    chat_id: ChatId,
+    config: &Config,
+    scanner: &Scanner,
+    hash: &TxHash,
+) -> ResponseResult<()> {
+    match scanner.transaction(hash).await {
+        Ok(transaction) => {
+            let text = transaction_html(&transaction_report(&transaction, &config.links));
+            reply_html(bot, chat_id, vec![text.clone()], to_plain_text(&text), None).await
+        }
+        Err(e) if e.is_not_found() => {
+            warn!("Transaction {} not found", hash);
+            bot.send_message(chat_id, "Transaction not found").await?;
+            Ok(())
+        }
+        Err(e) => {
+            error!("Error fetching transaction {}: {}", hash, e);
+            bot.send_message(
+                chat_id,
+                "ApeChain is unavailable right now, please try again later.",
+            )
+            .await?;
+            Ok(())
+        }
     }
 }
 
-// async fn get_token_audit(client: Client, api_key: &str, api_plan: &str, token_address: &str) -> Result<TokenAudit, serde_json::Error> {
-//     let url = format!(
-//         "https://public-api.dextools.io/{}/v2/token/{}/{}/audit",
-//         api_plan, "apechain", token_address
-//     );
-//     let response = client
-//     .get(&url)
-//     .header("X-API-KEY", api_key)
-//     .send()
-//     .await
-//     .unwrap();
-
-//     let text = response.text().await.unwrap();
-//     match serde_json::from_str(&text) {
-//         Ok(obj) => Ok(obj),
-//         Err(e) =>  Err(e),
-//     }
-// }
-
-async fn get_holders(
-    client: Client,
-    token_address: &str,
-) -> Result<TokenTopHolders, serde_json::Error> {
-    let url = format!("https://ape.express/api/tokens/{}/holders", token_address);
-
-    let response = client.get(&url).send().await.unwrap();
-
-    let text = response.text().await.unwrap();
-
-    let holders: TokenTopHolders = serde_json::from_str(&text).unwrap_or_default();
-    Ok(holders)
+/// Replies with a comparison table of the first [`MAX_COMPARED_TOKENS`]
+/// addresses. Wallets, pools and other contracts are listed below the
+/// table as not being tokens.
+async fn answer_batch(
+    bot: &Bot,
+    chat_id: ChatId,
+    config: &Config,
+    scanner: &Scanner,
+    token_addresses: &[Address],
+) -> ResponseResult<()> {
+    let compared = &token_addresses[..token_addresses.len().min(MAX_COMPARED_TOKENS)];
+    let scans = scanner.scan_many(compared).await;
+    let comparison = compare(token_addresses, scans, &config.links);
+    let text = comparison_html(&comparison);
+    let parts = html::split(&text, TELEGRAM_MESSAGE_LIMIT);
+    reply_html(bot, chat_id, parts, html::to_plain_text(&text), None).await
 }
 
-async fn make_token_overview_message(
-    token_info: &TokenInfo,
-    token_price_history: &TokenPriceHistory,
-    token_top_holders: &TokenTopHolders,
-    // token_audit: &TokenAudit,
-) -> Result<String, reqwest::Error> {
-    let token_decimal = 18;
-
-    // Get native token price
-    let native_token_price = match get_native_token_price().await {
-        Ok(token) => token.price.parse::<f64>().unwrap_or_default() / 10_f64.powi(8),
-        Err(_) => 0.0,
+/// Replies with the full overview of a token, or with one `section` of it.
+async fn answer_token(
+    bot: &Bot,
+    chat_id: ChatId,
+    config: &Config,
+    scanner: &Scanner,
+    token_adr: &Address,
+    section: Option<Section>,
+) -> ResponseResult<()> {
+    let Some(overview) = scan_overview(bot, chat_id, config, scanner, token_adr).await? else {
+        return Ok(());
     };
+    match section {
+        Some(section) => {
+            let parts = section_messages(&overview, section);
+            let plain = to_plain_text(&section_html(&overview, section));
+            reply_html(bot, chat_id, parts, plain, None).await
+        }
+        None => {
+            let parts = telegram_messages(&overview);
+            let keyboard = overview_keyboard(&overview);
+            reply_html(bot, chat_id, parts, plain_text(&overview), Some(keyboard)).await
+        }
+    }
+}
 
-    // Extract token info with proper error handling
-    let token_address = &token_info.address;
-    // let token_launch_at = &token_info.launch_at;
-    let token_name = &token_info.name;
-    let token_symbol = &token_info.symbol;
-    let token_total_supply = token_info.total_supply.parse::<f64>().unwrap_or_default()
-        / 10_f64.powi(token_decimal as i32);
-    let token_block_timestamp = &token_info.block_timestamp;
-    let token_price = num_floating_point(
-        &(token_info.price.parse::<f64>().unwrap_or_default() * native_token_price),
-        5,
-    );
-    let token_liquidity = token_info.liquidity.clone().unwrap_or_default();
-    let liquidity = controll_big_float(
-        token_liquidity
-            .native_reserve
-            .parse::<f64>()
-            .unwrap_or_default()
-            / 10_f64.powi(token_decimal as i32)
-            * native_token_price
-            * 2.0,
-    );
-
-    let market_cap = controll_big_float(token_total_supply * token_price);
-    let age = if let Some(block_time) = token_block_timestamp {
-        calculate_age(block_time)
-    } else {
-        "🔥".to_string()
+/// Posts a live overview, pins it and keeps it updated in the background,
+/// replacing the chat's previous live overview.
+async fn answer_pin(
+    bot: &Bot,
+    chat_id: ChatId,
+    config: Arc<Config>,
+    scanner: Arc<Scanner>,
+    live_pins: &LivePins,
+    token_adr: Address,
+) -> ResponseResult<()> {
+    let Some(overview) = scan_overview(bot, chat_id, &config, &scanner, &token_adr).await? else {
+        return Ok(());
+    };
+    let Some(text) = telegram_single_message(&overview) else {
+        bot.send_message(
+            chat_id,
+            "This overview is too long to keep in a single message.",
+        )
+        .await?;
+        return Ok(());
     };
 
-    //social info
-    let mut social_text = String::new();
-    if let Some(details) = &token_info.details {
-        if let Some(discord) = &details.discord {
-            if !discord.is_empty() {
-                social_text += &format!(" <a href=\"{discord}\">💭 </a>");
-            }
+    let message = bot
+        .send_message(chat_id, text)
+        .parse_mode(ParseMode::Html)
+        .link_preview_options(link_preview_disabled())
+        .reply_markup(overview_keyboard(&overview))
+        .await?;
+    if let Err(e) = bot
+        .pin_chat_message(chat_id, message.id)
+        .disable_notification(true)
+        .await
+    {
+        warn!("Could not pin the live overview in {}: {}", chat_id, e);
+    }
+
+    // Holding the lock until the handle is stored means the task cannot
+    // finish and look for its own entry before it is there.
+    let mut pins = live_pins.lock().unwrap();
+    let task = tokio::spawn({
+        let bot = bot.clone();
+        let live_pins = live_pins.clone();
+        async move {
+            keep_pin_updated(bot, config, scanner, chat_id, message.id, token_adr).await;
+            forget_live_pin(&live_pins, chat_id, message.id);
         }
-        if let Some(telegram) = &details.telegram {
-            if !telegram.is_empty() {
-                social_text += &format!(" <a href=\"{telegram}\">🕊️ </a>");
-            }
+    });
+    if let Some((_, previous)) = pins.insert(chat_id, (message.id, task)) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+/// Removes the chat's live pin entry if it is still the one for
+/// `message_id`, so a task that ends on its own does not linger in the map.
+/// A newer pin in the same chat is left alone.
+fn forget_live_pin(live_pins: &LivePins, chat_id: ChatId, message_id: MessageId) {
+    let mut pins = live_pins.lock().unwrap();
+    if pins
+        .get(&chat_id)
+        .is_some_and(|(pinned, _)| *pinned == message_id)
+    {
+        pins.remove(&chat_id);
+    }
+}
+
+/// Re-renders a live overview every refresh interval until its lifetime
+/// ends or the message is deleted. Backs off when Telegram asks it to.
+async fn keep_pin_updated(
+    bot: Bot,
+    config: Arc<Config>,
+    scanner: Arc<Scanner>,
+    chat_id: ChatId,
+    message_id: MessageId,
+    token_adr: Address,
+) {
+    let deadline = Instant::now() + config.live_pin.lifetime;
+    loop {
+        tokio::time::sleep(config.live_pin.refresh_interval).await;
+        if Instant::now() >= deadline {
+            break;
         }
-        if let Some(twitter) = &details.twitter {
-            if !twitter.is_empty() {
-                social_text += &format!(" <a href=\"{twitter}\">𝕏 </a>");
+
+        let overview = match scanner.scan(&token_adr).await {
+            Ok(scan) => analyze(&scan, &config.links),
+            Err(e) => {
+                warn!("Error refreshing the live overview of {}: {}", token_adr, e);
+                continue;
             }
-        }
-        if let Some(website) = &details.website {
-            if !website.is_empty() {
-                social_text += &format!(" <a href=\"{website}\">🌐 </a>");
+        };
+        let Some(text) = telegram_single_message(&overview) else {
+            continue;
+        };
+        let edited = bot
+            .edit_message_text(chat_id, message_id, text)
+            .parse_mode(ParseMode::Html)
+            .link_preview_options(link_preview_disabled())
+            .reply_markup(overview_keyboard(&overview))
+            .await;
+        match edited {
+            Ok(_) | Err(RequestError::Api(ApiError::MessageNotModified)) => {}
+            Err(RequestError::RetryAfter(wait)) => tokio::time::sleep(wait.duration()).await,
+            Err(RequestError::Api(
+                ApiError::MessageToEditNotFound | ApiError::MessageIdInvalid,
+            )) => {
+                info!(
+                    "Live overview of {} in {} was deleted, stopping",
+                    token_adr, chat_id
+                );
+                return;
             }
+            Err(e) => warn!("Error editing the live overview of {}: {}", token_adr, e),
         }
     }
+    info!("Live overview of {} in {} expired", token_adr, chat_id);
+}
 
-    //top price history
-    // let price = num_floating_point(&token_price_history.data.price, 3)  ;
-    let price_1h = num_floating_point(&token_price_history.data.price_1h.unwrap_or_default(), 3);
-    let price_6h = num_floating_point(&token_price_history.data.price_6h.unwrap_or_default(), 3);
-    let price_24h = num_floating_point(&token_price_history.data.price_24h.unwrap_or_default(), 3);
-    let variation_1h = num_floating_point(
-        &token_price_history.data.variation_1h.unwrap_or_default(),
-        2,
-    );
-    let variation_6h = num_floating_point(
-        &token_price_history.data.variation_6h.unwrap_or_default(),
-        2,
-    );
-    let variation_24h = num_floating_point(
-        &token_price_history.data.variation_24h.unwrap_or_default(),
-        2,
-    );
-
-    //top holders Info
-    let holders_count = token_top_holders
-        .total_holders
-        .parse::<u32>()
-        .unwrap_or_default();
-    let mut sum_usd_amount_top_10_holders = 0.0;
-    let mut holders_text = String::from("\n");
-    let mut top_num = 0;
-    let mut index_on_a_line = 0;
-    let mut num_whale = 0;
-    let mut num_largefish = 0;
-    let mut num_bigfish = 0;
-    let mut num_smallfish = 0;
-    let mut num_shrimp = 0;
-
-    if holders_count >= 50 {
-        holders_text += &format!("<u><b><i>50 Top Holders Map</i></b></u>\n        ");
-    } else if holders_count > 0 {
-        holders_text += &format!("<u><b><i>{holders_count} Top Holders Map</i></b></u>\n        ");
-    }
-    for holder in &token_top_holders.list {
-        let holder_address = &holder.address;
-        let balance = holder.balance.parse::<f64>().unwrap_or_default();
-        let usd_amount = balance / 10_f64.powi(token_decimal as i32) * token_price;
-
-        top_num += 1;
-        if top_num <= 10 {
-            sum_usd_amount_top_10_holders += usd_amount;
+/// The token addresses in a message, in order. Warns in the chat about any
+/// written in mixed case that fails its EIP-55 checksum, since those are
+/// likely mistyped, but still returns them.
+async fn addresses_in(bot: &Bot, chat_id: ChatId, text: &str) -> ResponseResult<Vec<Address>> {
+    let mut addresses = Vec::new();
+    for raw in extract_addresses(text) {
+        let Ok((address, checksum)) = Address::parse(&raw) else {
+            continue;
+        };
+        if checksum == Checksum::Invalid {
+            warn!("Address {} fails its EIP-55 checksum", raw);
+            bot.send_message(
+                chat_id,
+                format!(
+                    "⚠️ <code>{}</code> does not match its checksum and may be mistyped. Did you mean <code>{}</code>?",
+                    html::escape(&raw),
+                    address.checksummed()
+                ),
+            )
+            .parse_mode(ParseMode::Html)
+            .await?;
         }
+        addresses.push(address);
+    }
+    Ok(addresses)
+}
 
-        let whale_symbol = if usd_amount > 100000.0 {
-            num_whale += 1;
-            "🐳"
-        } else if usd_amount > 50000.0 {
-            num_largefish += 1;
-            "🦈"
-        } else if usd_amount > 10000.0 {
-            num_bigfish += 1;
-            "🐬"
-        } else if usd_amount > 1000.0 {
-            num_smallfish += 1;
-            "🐟"
-        } else {
-            num_shrimp += 1;
-            "🦐"
-        };
+/// The transaction hashes in a message, in order.
+fn tx_hashes_in(text: &str) -> Vec<TxHash> {
+    extract_tx_hashes(text)
+        .iter()
+        .filter_map(|raw| raw.parse().ok())
+        .collect()
+}
 
-        let link = format!("<a href=\"https://apescan.io/address/{holder_address}?Amount={usd_amount}\">{whale_symbol}</a>");
-        if index_on_a_line == 9 {
-            holders_text = holders_text + &link + "\n        ";
-            index_on_a_line = 0;
-        } else {
-            holders_text = holders_text + &link;
-            index_on_a_line += 1;
+/// The first address in a command's argument, or `None` after telling the
+/// user one is needed.
+async fn command_address(
+    bot: &Bot,
+    chat_id: ChatId,
+    argument: &str,
+) -> ResponseResult<Option<Address>> {
+    let address = addresses_in(bot, chat_id, argument)
+        .await?
+        .into_iter()
+        .next();
+    if address.is_none() {
+        bot.send_message(chat_id, "Please add an address after the command.")
+            .await?;
+    }
+    Ok(address)
+}
+
+/// Scans a token and analyzes it, replying with an error message and
+/// returning `None` when the scan fails.
+async fn scan_overview(
+    bot: &Bot,
+    chat_id: ChatId,
+    config: &Config,
+    scanner: &Scanner,
+    token_adr: &Address,
+) -> ResponseResult<Option<TokenOverview>> {
+    match scanner.scan(token_adr).await {
+        Ok(scan) => return Ok(Some(analyze(&scan, &config.links))),
+        Err(e) if e.is_not_found() => {
+            warn!("Token {} not found: {}", token_adr, e);
+            bot.send_message(chat_id, "Token not found").await?;
         }
-        if holders_count <= 50 {
-            if top_num == holders_count {
-                holders_text += &format!("\n        🐳 ( > $100K ) :  {num_whale}\n        🦈 ( $50K - $100K ) :  {num_largefish}\n        🐬 ( $10K - $50K ) :  {num_bigfish}\n        🐟 ( $1K - $10K ) :  {num_smallfish}\n        🦐 ( $0 - $1K ) :  {num_shrimp}\n");
-            }
-        } else {
-            if top_num == 50 {
-                holders_text += &format!("\n        🐳 ( > $100K ) :  {num_whale}\n        🦈 ( $50K - $100K ) :  {num_largefish}\n        🐬 ( $10K - $50K ) :  {num_bigfish}\n        🐟 ( $1K - $10K ) :  {num_smallfish}\n        🦐 ( $0 - $1K ) :  {num_shrimp}\n");
-                break;
-            }
+        Err(FetchError::Decode(e)) => {
+            error!("Error decoding token info: {}", e);
+            bot.send_message(chat_id, "Invalid token address").await?;
+        }
+        Err(e) => {
+            error!("Error fetching token overview: {}", e);
+            bot.send_message(
+                chat_id,
+                "ape.express is unavailable right now, please try again later.",
+            )
+            .await?;
         }
     }
-
-    let percentage_top_10_holders = if sum_usd_amount_top_10_holders > 0.0 {
-        (sum_usd_amount_top_10_holders / token_total_supply / token_price * 100.0).round()
-    } else {
-        0.0
-    };
-    let percentage_top_10_holders = controll_big_float(percentage_top_10_holders);
-
-    // //token audit
-    // let mut audit_text = String::new();
-    // let token_audit_status = &token_audit.status_code;
-    // if *token_audit_status == 200 {
-    //     let is_open_source = &token_audit.data.is_open_source;
-    //     let is_honeypot = &token_audit.data.is_honeypot;
-    //     let is_mintable = &token_audit.data.is_mintable;
-    //     let is_proxy = &token_audit.data.is_proxy;
-    //     let slippage_modifiable = &token_audit.data.slippage_modifiable;
-    //     let is_blacklisted = &token_audit.data.is_blacklisted;
-    //     let is_contract_renounced = &token_audit.data.is_contract_renounced;
-    //     let is_potentially_scam = &token_audit.data.is_potentially_scam;
-
-    //     audit_text += &format!("🔍 Audit\n");
-    //     if is_open_source    == "yes" {
-    //         audit_text += &format!("        🔓 Open source: ✅\n");
-    //     } else if is_open_source == "no" {
-    //         audit_text += &format!("        🔓 Open source: ❌\n");
-    //     }
-    //     if is_honeypot == "yes" {
-    //         audit_text += &format!("        🍯 Honeypot: ✅\n");
-    //     } else if is_honeypot == "no" {
-    //         audit_text += &format!("        🍯 Honeypot: ❌\n");
-    //     }
-    //     if is_mintable == "yes" {
-    //         audit_text += &format!("        🖨 Mintable: ✅\n");
-    //     } else if is_mintable == "no" {
-    //         audit_text += &format!("        🖨 Mintable: ❌\n");
-    //     }
-    //     if is_proxy == "yes" {
-    //         audit_text += &format!("        🔄 Proxy: ✅\n");
-    //     } else if is_proxy == "no" {
-    //         audit_text += &format!("        🔄 Proxy: ❌\n");
-    //     }
-    //     if slippage_modifiable == "yes" {
-    //         audit_text += &format!("        📊 Slippage modifiable: ✅\n");
-    //     } else if slippage_modifiable == "no" {
-    //         audit_text += &format!("        📊 Slippage modifiable: ❌\n");
-    //     }
-    //     if is_blacklisted == "yes" {
-    //         audit_text += &format!("        ⛔ Blacklisted: ❗\n");
-    //     } else if is_blacklisted == "no" {
-    //         audit_text += &format!("        ⛔ Blacklisted: ❌\n");
-    //     }
-    //     if is_contract_renounced == "yes" {
-    //         audit_text += &format!("        📜 Contract renounced: ✅\n");
-    //     } else if is_contract_renounced == "no" {
-    //         audit_text += &format!("        📜 Contract renounced: ❌\n");
-    //     }
-    //     if is_potentially_scam == "yes" {
-    //         audit_text += &format!("        ⚠️ Potentially scam: ❗\n");
-    //     } else if is_potentially_scam == "no" {
-    //         audit_text += &format!("        ⚠️ Potentially scam: ❌\n");
-    //     }
-    // }
-
-    let text = format!("
-<a href=\"https://dexscreener.com/apechain/{token_address}\">🚀</a> {token_name}  ${token_symbol}
-💰 USD:  ${token_price}
-💎 Mcap:  ${market_cap}
-💦 Liquidity:  ${liquidity}
-📈 Price history
-        └ <i>1H:</i>    ${price_1h} / {variation_1h}%  
-        └ <i>6H:</i>    ${price_6h} / {variation_6h}%  
-        └ <i>24H:</i>  ${price_24h} / {variation_24h}% 
-🕐 Age:  {age}
-🧰 More: {social_text}
-👩‍👧‍👦 Holders: {holders_count}
-        └ Top 10 Holders :  {percentage_top_10_holders}%
-{holders_text} 
-<code>{token_address}</code>
-<a href=\"https://ape.express/explore/{token_address}?\">AX</a> <a href=\"https://dexscreener.com/apechain/{token_address}\">DEX</a> <a href=\"https://apescan.io/address/{token_address}\">EXP</a>
-
-❎ <a href=\"https://twitter.com/search?q={token_address}=typed_query&f=live\"> Search on 𝕏 </a>
-📈 <a href=\"https://apescan.io/token/{token_address}\"> APE Scan </a>
-");
-
-    Ok(text)
+    Ok(None)
 }
 
-fn num_floating_point(num: &f64, length: i32) -> f64 {
-    ((num * 10_f64.powi(length as i32)).round()) / 10_f64.powi(length as i32)
+/// Sends an HTML reply, falling back to `plain` if Telegram cannot parse
+/// the HTML. The keyboard goes under the last message.
+async fn reply_html(
+    bot: &Bot,
+    chat_id: ChatId,
+    parts: Vec<String>,
+    plain: String,
+    keyboard: Option<InlineKeyboardMarkup>,
+) -> ResponseResult<()> {
+    match send_html_parts(bot, chat_id, parts, keyboard.clone()).await {
+        Err(RequestError::Api(ApiError::CantParseEntities(e))) => {
+            warn!(
+                "Telegram rejected the reply HTML, sending plain text: {}",
+                e
+            );
+            let mut request = bot
+                .send_message(chat_id, plain)
+                .link_preview_options(link_preview_disabled());
+            if let Some(keyboard) = keyboard {
+                request = request.reply_markup(keyboard);
+            }
+            request.await?;
+            Ok(())
+        }
+        sent => sent,
+    }
 }
 
-fn controll_big_float(num: f64) -> String {
-    if num > 1_000_000.0 {
-        format!("{:.1}M", num / 1_000_000.0)
-    } else if num > 1_000.0 {
-        format!("{:.2}K", num / 1000.0)
-    } else {
-        format!("{:.3}", num)
+/// Sends the parts of an HTML message in order, stopping at the first error.
+/// The keyboard goes under the last part.
+async fn send_html_parts(
+    bot: &Bot,
+    chat_id: ChatId,
+    parts: Vec<String>,
+    keyboard: Option<InlineKeyboardMarkup>,
+) -> ResponseResult<()> {
+    let last = parts.len().saturating_sub(1);
+    for (index, part) in parts.into_iter().enumerate() {
+        let mut request = bot
+            .send_message(chat_id, part)
+            .parse_mode(ParseMode::Html)
+            .link_preview_options(link_preview_disabled());
+        if index == last {
+            if let Some(keyboard) = &keyboard {
+                request = request.reply_markup(keyboard.clone());
+            }
+        }
+        request.await?;
     }
+    Ok(())
 }
 
-fn calculate_age(timestamp: &str) -> String {
-    if let Ok(unix_timestamp) = timestamp.parse::<i64>() {
-        let creation = DateTime::<Utc>::from_timestamp(unix_timestamp, 0).unwrap();
-        let now = Utc::now();
-        let duration = now.signed_duration_since(creation);
-
-        let days = duration.num_days();
-        if days > 365 {
-            format!("{:.1} years", days as f64 / 365.0)
-        } else if days > 30 {
-            format!("{:.1} months", days as f64 / 30.0)
-        } else {
-            format!("{} days", days)
-        }
-    } else {
-        "🔥".to_string()
+/// The buttons under an overview message.
+fn overview_keyboard(overview: &TokenOverview) -> InlineKeyboardMarkup {
+    let address = &overview.metrics.address;
+    let button = |text: &str, action: OverviewAction| {
+        InlineKeyboardButton::callback(text, action.callback_data(address))
+    };
+    let mut second_row = vec![button("💰 Price in APE", OverviewAction::PriceInApe)];
+    if let Ok(chart) = overview.links.dexscreener.parse() {
+        second_row.insert(0, InlineKeyboardButton::url("📈 Chart", chart));
     }
+    InlineKeyboardMarkup::new([
+        vec![
+            button("🔄 Refresh", OverviewAction::Refresh),
+            button("👩‍👧‍👦 Holders", OverviewAction::Holders),
+            button("🔍 Audit", OverviewAction::Audit),
+        ],
+        second_row,
+    ])
 }
 
-async fn get_native_token_price() -> Result<NativeToken, serde_json::Error> {
-    let client = Client::new();
-    let url = "https://ape.express/api/tokens/ape".to_string();
+/// Handles the buttons under an overview message. Refresh edits the
+/// message in place; the other actions reply below it or in a popup.
+async fn callback_handler(
+    bot: Bot,
+    q: CallbackQuery,
+    config: Arc<Config>,
+    scanner: Arc<Scanner>,
+) -> ResponseResult<()> {
+    let action = q
+        .data
+        .as_deref()
+        .and_then(OverviewAction::parse)
+        .and_then(|(action, token_adr)| Some((action, token_adr.parse::<Address>().ok()?)));
+    let (Some((action, token_adr)), Some(message)) = (action, q.message.as_ref()) else {
+        bot.answer_callback_query(q.id).await?;
+        return Ok(());
+    };
+    let chat_id = message.chat().id;
+
+    let overview = match scanner.scan(&token_adr).await {
+        Ok(scan) => analyze(&scan, &config.links),
+        Err(e) => {
+            error!("Error fetching token overview for a button: {}", e);
+            bot.answer_callback_query(q.id)
+                .text("Could not fetch the token right now, please try again later.")
+                .await?;
+            return Ok(());
+        }
+    };
 
-    let response = client.get(&url).send().await.unwrap();
+    match action {
+        OverviewAction::Refresh => {
+            // Always a single edit, never new messages: the compact
+            // overview when it fits, else a truncated one.
+            let (text, truncated) = telegram_truncated_message(&overview);
+            let notice = if truncated {
+                "Updated, details truncated to fit one message"
+            } else {
+                "Updated"
+            };
+            let edited = bot
+                .edit_message_text(chat_id, message.id(), text)
+                .parse_mode(ParseMode::Html)
+                .link_preview_options(link_preview_disabled())
+                .reply_markup(overview_keyboard(&overview))
+                .await;
+            match edited {
+                Ok(_) | Err(RequestError::Api(ApiError::MessageNotModified)) => {}
+                Err(e) => {
+                    bot.answer_callback_query(q.id)
+                        .text("Could not update the overview, please try again later.")
+                        .await?;
+                    return Err(e);
+                }
+            }
+            bot.answer_callback_query(q.id).text(notice).await?;
+        }
+        OverviewAction::Holders | OverviewAction::Audit => {
+            let section = if action == OverviewAction::Holders {
+                Section::Holders
+            } else {
+                Section::Audit
+            };
+            let parts = section_messages(&overview, section);
+            let plain = to_plain_text(&section_html(&overview, section));
+            reply_html(&bot, chat_id, parts, plain, None).await?;
+            bot.answer_callback_query(q.id).await?;
+        }
+        OverviewAction::PriceInApe => {
+            let text: String = price_in_ape_text(&overview).chars().take(200).collect();
+            bot.answer_callback_query(q.id)
+                .text(text)
+                .show_alert(true)
+                .await?;
+        }
+    }
+    Ok(())
+}
 
-    let text = response.text().await.unwrap();
-    match serde_json::from_str(&text) {
-        Ok(obj) => Ok(obj),
-        Err(e) => Err(e),
+fn link_preview_disabled() -> LinkPreviewOptions {
+    LinkPreviewOptions {
+        is_disabled: true,
+        prefer_small_media: false,
+        prefer_large_media: false,
+        show_above_text: false,
+        url: None,
     }
 }
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::price_feed::NativePriceStatus;
+use crate::scan::ScanData;
+use crate::token_amount::TokenAmount;
+
+/// Decimals of the native APE token, used for liquidity reserves.
+pub const NATIVE_DECIMALS: u32 = 18;
+
+/// How many of the top holders the overview looks at.
+pub const TOP_HOLDERS_LIMIT: usize = 50;
+
+/// Every number the overview shows, computed from a [`ScanData`] without any
+/// formatting. USD figures are `None` when the native APE price is unknown.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverviewMetrics {
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    /// `true` when DexTools did not report the token's decimals and
+    /// `decimals` is an assumed default, which skews the supply, market cap
+    /// and holder USD values.
+    pub decimals_assumed: bool,
+    /// Exact total supply in whole tokens.
+    pub total_supply: String,
+    pub native_price_usd: Option<f64>,
+    pub native_price_stale: bool,
+    /// Seconds since the native price was last refreshed.
+    pub native_price_age_secs: Option<u64>,
+    /// Token price in APE, as reported by ape.express.
+    pub price_native: Option<f64>,
+    /// Unrounded; the renderers round it for display.
+    pub price_usd: Option<f64>,
+    pub market_cap_usd: Option<f64>,
+    /// `None` also while the token has no DEX pool, e.g. during its bonding
+    /// curve.
+    pub liquidity_usd: Option<f64>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub age_days: Option<i64>,
+    pub price_history: Option<PriceChanges>,
+    pub holders: Option<HolderMetrics>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceChanges {
+    pub price_1h: Option<f64>,
+    pub variation_1h: Option<f64>,
+    pub price_6h: Option<f64>,
+    pub variation_6h: Option<f64>,
+    pub price_24h: Option<f64>,
+    pub variation_24h: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HolderMetrics {
+    pub total: u32,
+    /// Share of the total supply held by the top 10 holders, in percent.
+    pub top_10_percent: f64,
+    /// Share of the total supply held by the creator, in percent; `None`
+    /// when the creator is not among the top holders.
+    pub creator_percent: Option<f64>,
+    /// Holder counts per USD tier among the top holders; `None` when the
+    /// price is unknown.
+    pub tiers: Option<HolderTiers>,
+}
+
+/// USD size buckets used for the holders map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolderTier {
+    Whale,
+    LargeFish,
+    BigFish,
+    SmallFish,
+    Shrimp,
+}
+
+impl HolderTier {
+    pub fn from_usd(usd_amount: f64) -> Self {
+        if usd_amount > 100000.0 {
+            HolderTier::Whale
+        } else if usd_amount > 50000.0 {
+            HolderTier::LargeFish
+        } else if usd_amount > 10000.0 {
+            HolderTier::BigFish
+        } else if usd_amount > 1000.0 {
+            HolderTier::SmallFish
+        } else {
+            HolderTier::Shrimp
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct HolderTiers {
+    pub whale: u32,
+    pub large_fish: u32,
+    pub big_fish: u32,
+    pub small_fish: u32,
+    pub shrimp: u32,
+}
+
+impl HolderTiers {
+    pub fn add(&mut self, tier: HolderTier) {
+        match tier {
+            HolderTier::Whale => self.whale += 1,
+            HolderTier::LargeFish => self.large_fish += 1,
+            HolderTier::BigFish => self.big_fish += 1,
+            HolderTier::SmallFish => self.small_fish += 1,
+            HolderTier::Shrimp => self.shrimp += 1,
+        }
+    }
+}
+
+/// USD price of a token from its APE price, unrounded so dust prices still
+/// carry into market caps and holder values. `None` when the native APE
+/// price is unknown; an unknown token price counts as 0.
+pub fn token_price_usd(price_native: Option<f64>, native_price_usd: Option<f64>) -> Option<f64> {
+    native_price_usd.map(|native| price_native.unwrap_or_default() * native)
+}
+
+impl OverviewMetrics {
+    pub fn from_scan(scan: &ScanData) -> Self {
+        let token_info = &scan.token_info;
+        let decimals = scan.decimals();
+        let native_price_usd = scan.native_price.usd();
+
+        let price_native = token_info.price.parse::<f64>().ok();
+        let price_usd = token_price_usd(price_native, native_price_usd);
+        let market_cap_usd =
+            price_usd.map(|price| token_info.total_supply.to_f64(decimals) * price);
+        let liquidity_usd =
+            token_info
+                .liquidity
+                .as_ref()
+                .zip(native_price_usd)
+                .map(|(liquidity, native)| {
+                    liquidity.native_reserve.to_f64(NATIVE_DECIMALS) * native * 2.0
+                });
+
+        let created_at = token_info
+            .block_timestamp
+            .as_deref()
+            .and_then(|timestamp| timestamp.parse::<i64>().ok())
+            .and_then(|timestamp| DateTime::<Utc>::from_timestamp(timestamp, 0));
+        let age_days = created_at.map(|created_at| (Utc::now() - created_at).num_days());
+
+        let price_history = scan.price_history.as_ref().map(|history| PriceChanges {
+            price_1h: history.data.price_1h,
+            variation_1h: history.data.variation_1h,
+            price_6h: history.data.price_6h,
+            variation_6h: history.data.variation_6h,
+            price_24h: history.data.price_24h,
+            variation_24h: history.data.variation_24h,
+        });
+
+        let holders = scan.holders.as_ref().map(|holders| {
+            let top_10_balance: TokenAmount = holders
+                .list
+                .iter()
+                .take(10)
+                .map(|holder| &holder.balance)
+                .sum();
+            let tiers = price_usd.map(|price| {
+                let mut tiers = HolderTiers::default();
+                for holder in holders.list.iter().take(TOP_HOLDERS_LIMIT) {
+                    tiers.add(HolderTier::from_usd(
+                        holder.balance.to_f64(decimals) * price,
+                    ));
+                }
+                tiers
+            });
+            let creator_percent = holders
+                .list
+                .iter()
+                .find(|holder| holder.address.eq_ignore_ascii_case(&token_info.creator))
+                .map(|creator| creator.balance.percent_of(&token_info.total_supply));
+            HolderMetrics {
+                total: holders.total_holders.parse().unwrap_or_default(),
+                top_10_percent: top_10_balance.percent_of(&token_info.total_supply),
+                creator_percent,
+                tiers,
+            }
+        });
+
+        Self {
+            address: token_info.address.clone(),
+            name: token_info.name.clone(),
+            symbol: token_info.symbol.clone(),
+            decimals,
+            decimals_assumed: scan.decimals_assumed(),
+            total_supply: token_info.total_supply.format_units(decimals),
+            native_price_usd,
+            native_price_stale: matches!(scan.native_price, NativePriceStatus::Stale(_)),
+            native_price_age_secs: match scan.native_price {
+                NativePriceStatus::Fresh(price) | NativePriceStatus::Stale(price) => {
+                    Some(price.age().as_secs())
+                }
+                NativePriceStatus::Unavailable => None,
+            },
+            price_native,
+            price_usd,
+            market_cap_usd,
+            liquidity_usd,
+            created_at,
+            age_days,
+            price_history,
+            holders,
+        }
+    }
+}
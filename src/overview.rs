@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+
+use crate::config::LinkConfig;
+use crate::render::telegram_html;
+use crate::report::analyze;
+use crate::scan::ScanData;
+
+/// Renders the Telegram HTML overview for a completed scan.
+pub fn make_token_overview_message(links: &LinkConfig, scan: &ScanData) -> String {
+    telegram_html(&analyze(scan, links))
+}
+
+pub fn num_floating_point(num: &f64, length: i32) -> f64 {
+    ((num * 10_f64.powi(length)).round()) / 10_f64.powi(length)
+}
+
+pub fn controll_big_float(num: f64) -> String {
+    if num > 1_000_000.0 {
+        format!("{:.1}M", num / 1_000_000.0)
+    } else if num > 1_000.0 {
+        format!("{:.2}K", num / 1000.0)
+    } else {
+        format!("{:.3}", num)
+    }
+}
+
+/// Human-readable age of a unix `timestamp` in seconds, or `None` when it
+/// is not a number or out of range.
+pub fn calculate_age(timestamp: &str) -> Option<String> {
+    let creation = timestamp
+        .parse::<i64>()
+        .ok()
+        .and_then(|timestamp| DateTime::<Utc>::from_timestamp(timestamp, 0))?;
+    Some(format_age((Utc::now() - creation).num_days()))
+}
+
+/// `0x1234…abcd` for a full address; anything shorter is returned as is.
+pub fn short_address(address: &str) -> String {
+    match (
+        address.get(..6),
+        address.get(address.len().saturating_sub(4)..),
+    ) {
+        (Some(head), Some(tail)) if address.len() > 12 => format!("{head}…{tail}"),
+        _ => address.to_string(),
+    }
+}
+
+/// A USD price short enough for a table cell: two decimals from $1 up,
+/// six significant-looking decimals below, scientific notation for dust.
+pub fn format_price(price: f64) -> String {
+    if price >= 1.0 {
+        format!("${price:.2}")
+    } else if price >= 0.0001 {
+        format!("${price:.6}")
+    } else if price > 0.0 {
+        format!("${price:.2e}")
+    } else {
+        "$0".to_string()
+    }
+}
+
+/// Human-readable token age, e.g. `12 days`, `2.5 months` or `1.2 years`.
+pub fn format_age(days: i64) -> String {
+    if days > 365 {
+        format!("{:.1} years", days as f64 / 365.0)
+    } else if days > 30 {
+        format!("{:.1} months", days as f64 / 30.0)
+    } else {
+        format!("{} days", days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculates_age_of_a_valid_timestamp() {
+        let ten_days_ago = (Utc::now() - chrono::Duration::days(10)).timestamp();
+        assert_eq!(
+            calculate_age(&ten_days_ago.to_string()).as_deref(),
+            Some("10 days")
+        );
+    }
+
+    #[test]
+    fn age_of_an_invalid_timestamp_is_unknown() {
+        assert_eq!(calculate_age("soon"), None);
+        assert_eq!(calculate_age(""), None);
+        assert_eq!(calculate_age(&i64::MAX.to_string()), None);
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolInfo {
+    /// `None` when DexTools does not know the pool.
+    #[serde(default)]
+    pub data: Option<PoolInfoData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolInfoData {
+    pub address: String,
+    /// The token the pool trades; the side token is what it is quoted in.
+    #[serde(rename = "mainToken")]
+    pub main_token: PoolToken,
+    #[serde(rename = "sideToken")]
+    pub side_token: PoolToken,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolToken {
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+}
@@ -0,0 +1,112 @@
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use log::warn;
+
+use crate::client::{ApeExpressClient, FetchError};
+use crate::config::NativePriceConfig;
+
+/// The ape.express native price endpoint reports USD with 8 implied decimals.
+const NATIVE_PRICE_DECIMALS: i32 = 8;
+
+/// A native APE/USD price and when it was fetched.
+#[derive(Debug, Clone, Copy)]
+pub struct NativePrice {
+    pub usd: f64,
+    pub updated_at: Instant,
+}
+
+impl NativePrice {
+    pub fn age(&self) -> Duration {
+        self.updated_at.elapsed()
+    }
+}
+
+/// What a scan knows about the native price at the moment it runs.
+#[derive(Debug, Clone, Copy)]
+pub enum NativePriceStatus {
+    Fresh(NativePrice),
+    /// The last refresh is older than the configured maximum age.
+    Stale(NativePrice),
+    /// No refresh has ever succeeded.
+    Unavailable,
+}
+
+impl NativePriceStatus {
+    /// The last known price, fresh or not.
+    pub fn usd(&self) -> Option<f64> {
+        match self {
+            NativePriceStatus::Fresh(price) | NativePriceStatus::Stale(price) => Some(price.usd),
+            NativePriceStatus::Unavailable => None,
+        }
+    }
+}
+
+/// Shared APE/USD price kept current by a background task, so scans never
+/// have to fetch it themselves.
+#[derive(Debug)]
+pub struct NativePriceFeed {
+    latest: RwLock<Option<NativePrice>>,
+    max_age: Duration,
+}
+
+impl NativePriceFeed {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            latest: RwLock::new(None),
+            max_age,
+        }
+    }
+
+    pub fn status(&self) -> NativePriceStatus {
+        match *self.latest.read().unwrap() {
+            Some(price) if price.age() <= self.max_age => NativePriceStatus::Fresh(price),
+            Some(price) => NativePriceStatus::Stale(price),
+            None => NativePriceStatus::Unavailable,
+        }
+    }
+
+    /// Fetches the price once and stores it on success.
+    pub async fn refresh(
+        &self,
+        ape_express: &ApeExpressClient,
+        timeout: Duration,
+    ) -> Result<(), FetchError> {
+        let token = tokio::time::timeout(timeout, ape_express.native_token_price())
+            .await
+            .unwrap_or(Err(FetchError::Timeout))?;
+        let usd =
+            token.price.parse::<f64>().unwrap_or_default() / 10_f64.powi(NATIVE_PRICE_DECIMALS);
+        if usd <= 0.0 {
+            warn!(
+                "Ignoring non-positive native token price: {:?}",
+                token.price
+            );
+            return Ok(());
+        }
+
+        *self.latest.write().unwrap() = Some(NativePrice {
+            usd,
+            updated_at: Instant::now(),
+        });
+        Ok(())
+    }
+
+    /// Spawns a task that refreshes the price every `config.refresh_interval`
+    /// for the lifetime of the process.
+    pub fn spawn_refresher(
+        self: Arc<Self>,
+        ape_express: ApeExpressClient,
+        config: NativePriceConfig,
+    ) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.refresh_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.refresh(&ape_express, config.timeout).await {
+                    warn!("Error refreshing native token price: {}", e);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,696 @@
+use crate::address::checksummed;
+use crate::compare::TokenComparison;
+use crate::html::{self, escape};
+use crate::metrics::{HolderTier, HolderTiers, OverviewMetrics};
+use crate::overview::{
+    controll_big_float, format_age, format_price, num_floating_point, short_address,
+};
+use crate::report::{AuditReport, TaxRange, TokenOverview};
+use crate::risk::{RiskAssessment, RiskLevel};
+use crate::transaction::{TransactionReport, TransactionStatus};
+use crate::wallet::WalletReport;
+
+/// Telegram's limit on the text of a single message.
+pub const TELEGRAM_MESSAGE_LIMIT: usize = 4096;
+
+/// How much of the top holders map to include in the Telegram message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HoldersMapLayout {
+    /// One explorer link per holder.
+    Linked,
+    /// The same emojis without links.
+    Compact,
+    /// Only the holder count and top 10 share.
+    Hidden,
+}
+
+/// Renders the overview as the Telegram HTML message posted by the bot.
+/// Every upstream-controlled string is escaped.
+pub fn telegram_html(overview: &TokenOverview) -> String {
+    telegram_html_with(overview, HoldersMapLayout::Linked)
+}
+
+/// Renders the overview as one or more Telegram HTML messages, in order,
+/// each within [`TELEGRAM_MESSAGE_LIMIT`]. A message that is too long first
+/// loses the holder links, then the holders map; only if it still does not
+/// fit is the full message split into parts.
+pub fn telegram_messages(overview: &TokenOverview) -> Vec<String> {
+    match telegram_single_message(overview) {
+        Some(text) => vec![text],
+        None => html::split(&telegram_html(overview), TELEGRAM_MESSAGE_LIMIT),
+    }
+}
+
+/// The fullest rendering of the overview that fits in one message, or
+/// `None` if even the one without a holders map is too long.
+pub fn telegram_single_message(overview: &TokenOverview) -> Option<String> {
+    [
+        HoldersMapLayout::Linked,
+        HoldersMapLayout::Compact,
+        HoldersMapLayout::Hidden,
+    ]
+    .into_iter()
+    .map(|layout| telegram_html_with(overview, layout))
+    .find(|text| html::telegram_len(text) <= TELEGRAM_MESSAGE_LIMIT)
+}
+
+/// Appended to an overview cut short by [`telegram_truncated_message`].
+const TRUNCATED_NOTICE: &str = "\n\n<i>✂️ Details truncated to fit one message</i>";
+
+/// The overview in exactly one message, for editing a message in place:
+/// [`telegram_single_message`] when it fits, else the start of the overview
+/// without a holders map followed by a note that the rest was cut. The flag
+/// is `true` when it was cut.
+pub fn telegram_truncated_message(overview: &TokenOverview) -> (String, bool) {
+    if let Some(text) = telegram_single_message(overview) {
+        return (text, false);
+    }
+    let limit = TELEGRAM_MESSAGE_LIMIT - html::telegram_len(TRUNCATED_NOTICE);
+    let hidden = telegram_html_with(overview, HoldersMapLayout::Hidden);
+    let head = html::split(&hidden, limit)
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+    (head.trim_end().to_string() + TRUNCATED_NOTICE, true)
+}
+
+fn telegram_html_with(overview: &TokenOverview, layout: HoldersMapLayout) -> String {
+    let metrics = &overview.metrics;
+    let links = &overview.links;
+    let token_address = escape(&checksummed(&metrics.address));
+    let token_name = escape(&metrics.name);
+    let token_symbol = escape(&metrics.symbol);
+
+    let mut usd_text = match metrics.price_usd {
+        Some(token_price) => {
+            let token_price = num_floating_point(&token_price, 5);
+            let market_cap = controll_big_float(metrics.market_cap_usd.unwrap_or_default());
+            let liquidity = match metrics.liquidity_usd {
+                Some(liquidity) => format!("${}", controll_big_float(liquidity)),
+                None => "no pool yet".to_string(),
+            };
+            let mut text = format!(
+                "💰 USD:  ${token_price}\n💎 Mcap:  ${market_cap}\n💦 Liquidity:  {liquidity}"
+            );
+            if metrics.native_price_stale {
+                let minutes = metrics.native_price_age_secs.unwrap_or_default() / 60;
+                text += &format!(
+                    "\n⚠️ <i>APE price stale, last updated {minutes} min ago</i>"
+                );
+            }
+            text
+        }
+        None => {
+            "💰 USD:  unavailable\n💎 Mcap:  unavailable\n💦 Liquidity:  unavailable\n⚠️ <i>APE price unavailable</i>".to_string()
+        }
+    };
+
+    if let Some(notice) = assumed_decimals_html(metrics) {
+        usd_text += &format!("\n{notice}");
+    }
+
+    //top price history
+    let price_history_text = price_history_html(metrics);
+
+    let age = match metrics.age_days {
+        Some(days) => format_age(days),
+        None => "🔥".to_string(),
+    };
+
+    //social info
+    let mut social_text = String::new();
+    let socials = &overview.socials;
+    for (link, icon) in [
+        (&socials.discord, "💭"),
+        (&socials.telegram, "🕊️"),
+        (&socials.twitter, "𝕏"),
+        (&socials.website, "🌐"),
+    ] {
+        if let Some(link) = link {
+            let link = escape(link);
+            social_text += &format!(" <a href=\"{link}\">{icon} </a>");
+        }
+    }
+
+    //top holders Info
+    let mut holders_text = String::from("\n");
+    let holders_summary_text = if let Some(holders) = &metrics.holders {
+        if let Some(tiers) = &holders.tiers {
+            if layout != HoldersMapLayout::Hidden {
+                holders_text += &holders_map(overview, tiers, layout);
+            }
+        }
+        let holders_count = holders.total;
+        let percentage_top_10_holders = controll_big_float(holders.top_10_percent.round());
+        format!(
+            "👩‍👧‍👦 Holders: {holders_count}\n        └ Top 10 Holders :  {percentage_top_10_holders}%"
+        )
+    } else {
+        "👩‍👧‍👦 Holders:  <i>temporarily unavailable</i>".to_string()
+    };
+
+    let audit_text = overview.audit.as_ref().map(audit_html).unwrap_or_default();
+    let risk_text = risk_section_html(&overview.risk);
+
+    let dexscreener_link = escape(&links.dexscreener);
+    let ape_express_link = escape(&links.ape_express);
+    let explorer_address_link = escape(&links.explorer_address);
+    let explorer_token_link = escape(&links.explorer_token);
+    let twitter_search_link = escape(&links.twitter_search);
+
+    format!("
+<a href=\"{dexscreener_link}\">🚀</a> {token_name}  ${token_symbol}
+{risk_text}{usd_text}
+{price_history_text}
+🕐 Age:  {age}
+🧰 More: {social_text}
+{audit_text}{holders_summary_text}
+{holders_text} 
+<code>{token_address}</code>
+<a href=\"{ape_express_link}\">AX</a> <a href=\"{dexscreener_link}\">DEX</a> <a href=\"{explorer_address_link}\">EXP</a>
+
+❎ <a href=\"{twitter_search_link}\"> Search on 𝕏 </a>
+📈 <a href=\"{explorer_token_link}\"> APE Scan </a>
+")
+}
+
+/// A warning, without a trailing newline, for an overview computed with
+/// assumed decimals; `None` when the token's own are known.
+pub(crate) fn assumed_decimals_html(metrics: &OverviewMetrics) -> Option<String> {
+    metrics.decimals_assumed.then(|| {
+        format!(
+            "⚠️ <i>Token decimals unknown, assuming {}: supply, Mcap and holder values may be off</i>",
+            metrics.decimals
+        )
+    })
+}
+
+/// The price history lines, without a trailing newline.
+pub(crate) fn price_history_html(metrics: &OverviewMetrics) -> String {
+    if let Some(history) = &metrics.price_history {
+        let price_1h = num_floating_point(&history.price_1h.unwrap_or_default(), 3);
+        let price_6h = num_floating_point(&history.price_6h.unwrap_or_default(), 3);
+        let price_24h = num_floating_point(&history.price_24h.unwrap_or_default(), 3);
+        let variation_1h = num_floating_point(&history.variation_1h.unwrap_or_default(), 2);
+        let variation_6h = num_floating_point(&history.variation_6h.unwrap_or_default(), 2);
+        let variation_24h = num_floating_point(&history.variation_24h.unwrap_or_default(), 2);
+        format!(
+            "📈 Price history
+        └ <i>1H:</i>    ${price_1h} / {variation_1h}%  
+        └ <i>6H:</i>    ${price_6h} / {variation_6h}%  
+        └ <i>24H:</i>  ${price_24h} / {variation_24h}% "
+        )
+    } else {
+        "📈 Price history:  <i>temporarily unavailable</i>".to_string()
+    }
+}
+
+/// The risk score followed by one line per warning. Ends with a newline.
+pub(crate) fn risk_section_html(risk: &RiskAssessment) -> String {
+    let mut text = format!(
+        "🛡 Risk:  <b>{}/100</b> {} {}\n",
+        risk.score,
+        risk_level_symbol(risk.level),
+        risk.level
+    );
+    for warning in &risk.warnings {
+        text += &format!("        └ ⚠️ {}\n", escape(&warning.message));
+    }
+    text
+}
+
+pub fn risk_level_symbol(level: RiskLevel) -> &'static str {
+    match level {
+        RiskLevel::Low => "🟢",
+        RiskLevel::Medium => "🟠",
+        RiskLevel::High => "🔴",
+    }
+}
+
+/// The audit section, one line per known flag and tax. Ends with a newline
+/// unless empty.
+pub(crate) fn audit_html(audit: &AuditReport) -> String {
+    let lines = audit_lines_html(audit);
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!("🔍 Audit\n{lines}")
+}
+
+/// One line per known flag and tax, each ending with a newline.
+pub(crate) fn audit_lines_html(audit: &AuditReport) -> String {
+    let mut text = String::new();
+    for (icon, label, value) in audit_lines(audit) {
+        let value = match value {
+            AuditValue::Flag {
+                set: true,
+                warning: true,
+            } => "❗".to_string(),
+            AuditValue::Flag { set: true, .. } => "✅".to_string(),
+            AuditValue::Flag { set: false, .. } => "❌".to_string(),
+            AuditValue::Tax(tax) => format_tax(tax),
+        };
+        text += &format!("        {icon} {label}: {value}\n");
+    }
+    text
+}
+
+enum AuditValue {
+    /// `warning` marks flags that are bad news when set.
+    Flag {
+        set: bool,
+        warning: bool,
+    },
+    Tax(TaxRange),
+}
+
+/// `(icon, label, value)` for every flag and tax DexTools knows.
+fn audit_lines(audit: &AuditReport) -> Vec<(&'static str, &'static str, AuditValue)> {
+    let flags = [
+        ("🔓", "Open source", audit.open_source, false),
+        ("🍯", "Honeypot", audit.honeypot, true),
+        ("🖨", "Mintable", audit.mintable, false),
+        ("🔄", "Proxy", audit.proxy, false),
+        (
+            "📊",
+            "Slippage modifiable",
+            audit.slippage_modifiable,
+            false,
+        ),
+        ("⛔", "Blacklisted", audit.blacklisted, true),
+        ("📜", "Contract renounced", audit.contract_renounced, false),
+        ("⚠️", "Potentially scam", audit.potentially_scam, true),
+    ];
+    let taxes = [("Buy tax", audit.buy_tax), ("Sell tax", audit.sell_tax)];
+
+    let flags = flags.into_iter().filter_map(|(icon, label, set, warning)| {
+        set.map(|set| (icon, label, AuditValue::Flag { set, warning }))
+    });
+    let taxes = taxes
+        .into_iter()
+        .filter_map(|(label, tax)| tax.map(|tax| ("💸", label, AuditValue::Tax(tax))));
+    flags.chain(taxes).collect()
+}
+
+pub(crate) fn format_tax(tax: TaxRange) -> String {
+    if tax.min == tax.max {
+        format!("{}%", num_floating_point(&tax.min, 2))
+    } else {
+        format!(
+            "{}% - {}%",
+            num_floating_point(&tax.min, 2),
+            num_floating_point(&tax.max, 2)
+        )
+    }
+}
+
+/// The emoji map of the top holders, ten per line, followed by the count
+/// per tier.
+pub(crate) fn holders_map(
+    overview: &TokenOverview,
+    tiers: &HolderTiers,
+    layout: HoldersMapLayout,
+) -> String {
+    let top_holders = &overview.top_holders;
+    if top_holders.is_empty() {
+        return String::new();
+    }
+
+    let mut text = format!(
+        "<u><b><i>{} Top Holders Map</i></b></u>\n        ",
+        top_holders.len()
+    );
+    for (index, holder) in top_holders.iter().enumerate() {
+        let whale_symbol = holder.tier.map(tier_symbol).unwrap_or("🦐");
+        if layout == HoldersMapLayout::Linked {
+            let holder_link = escape(&holder.explorer_link);
+            let usd_amount = holder.usd_amount.unwrap_or_default();
+            text += &format!("<a href=\"{holder_link}?Amount={usd_amount}\">{whale_symbol}</a>");
+        } else {
+            text += whale_symbol;
+        }
+        if index % 10 == 9 {
+            text += "\n        ";
+        }
+    }
+    text += &format!("\n        🐳 ( > $100K ) :  {}\n        🦈 ( $50K - $100K ) :  {}\n        🐬 ( $10K - $50K ) :  {}\n        🐟 ( $1K - $10K ) :  {}\n        🦐 ( $0 - $1K ) :  {}\n", tiers.whale, tiers.large_fish, tiers.big_fish, tiers.small_fish, tiers.shrimp);
+    text
+}
+
+pub fn tier_symbol(tier: HolderTier) -> &'static str {
+    match tier {
+        HolderTier::Whale => "🐳",
+        HolderTier::LargeFish => "🦈",
+        HolderTier::BigFish => "🐬",
+        HolderTier::SmallFish => "🐟",
+        HolderTier::Shrimp => "🦐",
+    }
+}
+
+/// Renders a comparison as a monospaced table with one row per token,
+/// followed by a link per token, any failed scans and, when addresses were
+/// left out, a "showing N of M" note.
+pub fn comparison_html(comparison: &TokenComparison) -> String {
+    let usd = |value: Option<f64>| match value {
+        Some(value) => format!("${}", controll_big_float(value)),
+        None => "-".to_string(),
+    };
+    let mut table = vec![[
+        "#".to_string(),
+        "Token".to_string(),
+        "Price".to_string(),
+        "Mcap".to_string(),
+        "Liq".to_string(),
+        "Holders".to_string(),
+        "24h".to_string(),
+    ]];
+    for (index, row) in comparison.rows.iter().enumerate() {
+        table.push([
+            (index + 1).to_string(),
+            row.symbol.chars().take(10).collect(),
+            row.price_usd
+                .map(format_price)
+                .unwrap_or_else(|| "-".to_string()),
+            usd(row.market_cap_usd),
+            usd(row.liquidity_usd),
+            row.holders
+                .map(|holders| holders.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            row.variation_24h
+                .map(|variation| format!("{variation:+.1}%"))
+                .unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    let mut widths = [0; 7];
+    for cells in &table {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut text = String::from("📊 <b>Token comparison</b>\n<pre>");
+    for cells in &table {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| {
+                let padding = " ".repeat(width - cell.chars().count());
+                format!("{}{padding}", escape(cell))
+            })
+            .collect();
+        text += line.join("  ").trim_end();
+        text += "\n";
+    }
+    text += "</pre>\n";
+
+    for (index, row) in comparison.rows.iter().enumerate() {
+        text += &format!(
+            "{}. <a href=\"{}\">{}</a>  <code>{}</code>\n",
+            index + 1,
+            escape(&row.dexscreener),
+            escape(&row.name),
+            escape(&checksummed(&row.address))
+        );
+    }
+    for failed in &comparison.failed {
+        text += &format!(
+            "⚠️ <code>{}</code>: {}\n",
+            escape(&short_address(&failed.address)),
+            failed.reason
+        );
+    }
+    if comparison.shown() < comparison.requested {
+        text += &format!(
+            "<i>Showing {} of {} tokens</i>\n",
+            comparison.shown(),
+            comparison.requested
+        );
+    }
+    text
+}
+
+/// Most token positions listed in a wallet summary; the rest are counted.
+const MAX_WALLET_POSITIONS: usize = 20;
+
+/// Renders a wallet summary as a Telegram HTML message. Every
+/// upstream-controlled string is escaped.
+pub fn wallet_html(wallet: &WalletReport) -> String {
+    let mut text = String::from("👛 <b>Wallet</b>\n");
+    text += &format!("<code>{}</code>\n", escape(&checksummed(&wallet.address)));
+    match (&wallet.username, &wallet.profile) {
+        (Some(username), Some(profile)) => {
+            text += &format!(
+                "👤 <b>ape.express:</b> <a href=\"{}\">{}</a>\n",
+                escape(profile),
+                escape(username)
+            )
+        }
+        (Some(username), None) => text += &format!("👤 <b>ape.express:</b> {}\n", escape(username)),
+        (None, Some(profile)) => {
+            text += &format!(
+                "👤 <b>ape.express:</b> <a href=\"{}\">profile</a>\n",
+                escape(profile)
+            )
+        }
+        (None, None) => {}
+    }
+    text += "\n";
+    text += &format!("💰 <b>Balance:</b> {:.4} APE", wallet.native_balance);
+    if let Some(usd) = wallet.native_balance_usd {
+        text += &format!(" (${})", controll_big_float(usd));
+    }
+    text += "\n";
+    text += &format!(
+        "📤 <b>Transactions sent:</b> {}\n",
+        wallet.transaction_count
+    );
+    if let Some(total) = wallet.total_usd {
+        text += &format!("💼 <b>Portfolio value:</b> ${}", controll_big_float(total));
+        let unvalued = wallet
+            .positions
+            .iter()
+            .flatten()
+            .filter(|position| position.balance.is_none())
+            .count();
+        if unvalued > 0 {
+            text += &format!(" <i>(excludes {unvalued} with unknown decimals)</i>");
+        }
+        text += "\n";
+    }
+
+    match &wallet.positions {
+        Some(positions) if positions.is_empty() => {
+            text += "\n<i>No ape.express tokens held</i>\n";
+        }
+        Some(positions) => {
+            text += "\n🪙 <b>ape.express tokens</b>\n";
+            for (index, position) in positions.iter().take(MAX_WALLET_POSITIONS).enumerate() {
+                text += &format!(
+                    "{}. <a href=\"{}\">{}</a>: {}",
+                    index + 1,
+                    escape(&position.ape_express_link),
+                    escape(&position.symbol),
+                    match &position.balance {
+                        Some(balance) => escape(balance),
+                        None => "<i>decimals unknown</i>".to_string(),
+                    }
+                );
+                if let Some(value) = position.value_usd {
+                    text += &format!(" (${})", controll_big_float(value));
+                }
+                text += "\n";
+            }
+            if positions.len() > MAX_WALLET_POSITIONS {
+                text += &format!(
+                    "<i>…and {} more</i>\n",
+                    positions.len() - MAX_WALLET_POSITIONS
+                );
+            }
+        }
+        None => text += "\n<i>ape.express holdings are unavailable right now</i>\n",
+    }
+
+    text += &format!(
+        "\n🔎 <a href=\"{}\">View on explorer</a>\n",
+        escape(&wallet.explorer_link)
+    );
+    text
+}
+
+/// Renders a transaction summary as a Telegram HTML message.
+pub fn transaction_html(transaction: &TransactionReport) -> String {
+    let status = match transaction.status {
+        TransactionStatus::Success => "✅ Success",
+        TransactionStatus::Failed => "❌ Failed",
+        TransactionStatus::Pending => "⏳ Pending",
+    };
+    let mut text = String::from("🧾 <b>Transaction</b>\n");
+    text += &format!("<code>{}</code>\n\n", escape(&transaction.hash));
+    text += &format!("<b>Status:</b> {status}\n");
+    if let Some(block) = transaction.block {
+        text += &format!("<b>Block:</b> {block}\n");
+    }
+    text += &format!(
+        "<b>From:</b> <code>{}</code>\n",
+        escape(&checksummed(&transaction.from))
+    );
+    match (&transaction.to, &transaction.contract_created) {
+        (Some(to), _) => text += &format!("<b>To:</b> <code>{}</code>\n", escape(&checksummed(to))),
+        (None, Some(contract)) => {
+            text += &format!(
+                "<b>Created contract:</b> <code>{}</code>\n",
+                escape(&checksummed(contract))
+            )
+        }
+        (None, None) => text += "<b>To:</b> contract creation\n",
+    }
+    text += &format!("<b>Value:</b> {} APE", transaction.value);
+    if let Some(usd) = transaction.value_usd.filter(|usd| *usd > 0.0) {
+        text += &format!(" (${})", controll_big_float(usd));
+    }
+    text += "\n";
+    if let Some(gas_used) = transaction.gas_used {
+        text += &format!("<b>Gas used:</b> {gas_used}\n");
+    }
+    text += &format!(
+        "\n🔎 <a href=\"{}\">View on explorer</a>\n",
+        escape(&transaction.explorer_link)
+    );
+    text
+}
+
+/// The token price in APE as a short plain-text note, e.g. for a callback
+/// query popup.
+pub fn price_in_ape_text(overview: &TokenOverview) -> String {
+    let metrics = &overview.metrics;
+    let mut text = match metrics.price_native {
+        Some(price) => format!("1 {} = {price} APE", metrics.symbol),
+        None => format!("The {} price in APE is unavailable", metrics.symbol),
+    };
+    if let Some(native) = metrics.native_price_usd {
+        text += &format!("\n1 APE = ${native:.4}");
+    }
+    text
+}
+
+/// Renders the overview as plain text for terminals and logs.
+pub fn plain_text(overview: &TokenOverview) -> String {
+    let metrics = &overview.metrics;
+    let usd = |value: Option<f64>| match value {
+        Some(value) => format!("${}", controll_big_float(value)),
+        None => "unavailable".to_string(),
+    };
+    let mut text = format!("{} ({})\n", metrics.name, metrics.symbol);
+    text += &format!("Address:      {}\n", checksummed(&metrics.address));
+    text += &risk_section_plain_text(&overview.risk);
+    text += &format!(
+        "Price:        {}\n",
+        match metrics.price_usd {
+            Some(price) => format!("${}", num_floating_point(&price, 5)),
+            None => "unavailable".to_string(),
+        }
+    );
+    text += &format!("Market cap:   {}\n", usd(metrics.market_cap_usd));
+    text += &format!(
+        "Liquidity:    {}\n",
+        match overview.liquidity {
+            Some(_) => usd(metrics.liquidity_usd),
+            None => "no pool yet".to_string(),
+        }
+    );
+    if metrics.native_price_stale {
+        text += "              (APE price is stale)\n";
+    }
+    text += &format!("Total supply: {}\n", metrics.total_supply);
+    if metrics.decimals_assumed {
+        text += &format!(
+            "              (decimals unknown, assuming {}; supply, market cap and holder values may be off)\n",
+            metrics.decimals
+        );
+    }
+    text += &price_history_plain_text(metrics);
+
+    match metrics.age_days {
+        Some(days) => text += &format!("Age:          {}\n", format_age(days)),
+        None => text += "Age:          unknown\n",
+    }
+
+    match &metrics.holders {
+        Some(holders) => {
+            text += &format!("Holders:      {}\n", holders.total);
+            text += &format!("Top 10 share: {:.2}%\n", holders.top_10_percent);
+            if let Some(tiers) = &holders.tiers {
+                text += &format!(
+                    "Top holders:  {} whale, {} large fish, {} big fish, {} small fish, {} shrimp\n",
+                    tiers.whale, tiers.large_fish, tiers.big_fish, tiers.small_fish, tiers.shrimp
+                );
+            }
+        }
+        None => text += "Holders:      unavailable\n",
+    }
+
+    if let Some(audit) = &overview.audit {
+        let lines = audit_lines(audit);
+        if !lines.is_empty() {
+            text += "Audit:\n";
+        }
+        for (_, label, value) in lines {
+            let value = match value {
+                AuditValue::Flag { set: true, .. } => "yes".to_string(),
+                AuditValue::Flag { set: false, .. } => "no".to_string(),
+                AuditValue::Tax(tax) => format_tax(tax),
+            };
+            text += &format!("  {:<22}{}\n", format!("{label}:"), value);
+        }
+    }
+
+    let socials = &overview.socials;
+    for (label, link) in [
+        ("Discord", &socials.discord),
+        ("Telegram", &socials.telegram),
+        ("Twitter", &socials.twitter),
+        ("Website", &socials.website),
+    ] {
+        if let Some(link) = link {
+            text += &format!("{:<14}{}\n", format!("{label}:"), link);
+        }
+    }
+
+    let links = &overview.links;
+    text += &format!("DEX Screener: {}\n", links.dexscreener);
+    text += &format!("ape.express:  {}\n", links.ape_express);
+    text += &format!("Explorer:     {}\n", links.explorer_token);
+    text
+}
+
+fn risk_section_plain_text(risk: &RiskAssessment) -> String {
+    let mut text = format!("Risk:         {}/100 ({})\n", risk.score, risk.level);
+    for warning in &risk.warnings {
+        text += &format!("  - {}\n", warning.message);
+    }
+    text
+}
+
+fn price_history_plain_text(metrics: &OverviewMetrics) -> String {
+    let Some(history) = &metrics.price_history else {
+        return "Price history: unavailable\n".to_string();
+    };
+    let mut text = String::new();
+    for (label, price, variation) in [
+        ("1h", history.price_1h, history.variation_1h),
+        ("6h", history.price_6h, history.variation_6h),
+        ("24h", history.price_24h, history.variation_24h),
+    ] {
+        text += &format!(
+            "Price {label:<4}   ${} / {:.2}%\n",
+            price.unwrap_or_default(),
+            variation.unwrap_or_default()
+        );
+    }
+    text
+}
+
+/// Renders the overview as a pretty-printed JSON document.
+pub fn json(overview: &TokenOverview) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(overview)
+}
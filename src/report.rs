@@ -0,0 +1,213 @@
+use serde::Serialize;
+
+use crate::config::LinkConfig;
+use crate::html::safe_url;
+use crate::metrics::{HolderTier, OverviewMetrics, NATIVE_DECIMALS, TOP_HOLDERS_LIMIT};
+use crate::risk::RiskAssessment;
+use crate::scan::ScanData;
+use crate::token_audit::{Tax, TokenAuditData};
+
+/// Everything a token overview shows, independent of how it is rendered.
+/// Produced by [`analyze`] and consumed by the renderers in
+/// [`crate::render`].
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenOverview {
+    pub metrics: OverviewMetrics,
+    pub risk: RiskAssessment,
+    /// The top holders in rank order, at most [`TOP_HOLDERS_LIMIT`].
+    pub top_holders: Vec<HolderEntry>,
+    pub socials: Socials,
+    /// The token's DEX pool; `None` while it has none, e.g. during its
+    /// bonding curve.
+    pub liquidity: Option<LiquidityReport>,
+    /// The DexTools security audit; `None` when it is unavailable or the
+    /// token has not been audited.
+    pub audit: Option<AuditReport>,
+    pub links: OverviewLinks,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HolderEntry {
+    pub address: String,
+    /// Exact balance in whole tokens.
+    pub balance: String,
+    pub percent_of_supply: f64,
+    pub usd_amount: Option<f64>,
+    pub tier: Option<HolderTier>,
+    pub explorer_link: String,
+}
+
+/// Social links from the token's ape.express profile. Empty links and
+/// anything that is not an absolute http(s) URL are `None`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Socials {
+    pub discord: Option<String>,
+    pub telegram: Option<String>,
+    pub twitter: Option<String>,
+    pub website: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LiquidityReport {
+    pub pair: String,
+    pub pair_link: String,
+    pub router: String,
+    /// Current APE reserve, in whole APE.
+    pub native_reserve: f64,
+    /// Current token reserve, in whole tokens.
+    pub token_reserve: f64,
+    /// APE reserve the pool was created with, in whole APE.
+    pub initial_native_reserve: f64,
+    /// Liquidity as a share of the market cap, in percent; `None` when
+    /// either is unknown.
+    pub market_cap_ratio: Option<f64>,
+}
+
+/// DexTools audit flags, `None` where DexTools reports them as unknown.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditReport {
+    pub open_source: Option<bool>,
+    pub honeypot: Option<bool>,
+    pub mintable: Option<bool>,
+    pub proxy: Option<bool>,
+    pub slippage_modifiable: Option<bool>,
+    pub blacklisted: Option<bool>,
+    pub contract_renounced: Option<bool>,
+    pub potentially_scam: Option<bool>,
+    pub buy_tax: Option<TaxRange>,
+    pub sell_tax: Option<TaxRange>,
+}
+
+/// Measured tax bounds, in percent.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TaxRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl AuditReport {
+    pub fn from_audit(data: &TokenAuditData) -> Self {
+        Self {
+            open_source: flag(&data.is_open_source),
+            honeypot: flag(&data.is_honeypot),
+            mintable: flag(&data.is_mintable),
+            proxy: flag(&data.is_proxy),
+            slippage_modifiable: flag(&data.slippage_modifiable),
+            blacklisted: flag(&data.is_blacklisted),
+            contract_renounced: flag(&data.is_contract_renounced),
+            potentially_scam: flag(&data.is_potentially_scam),
+            buy_tax: tax_range(&data.buy_tax),
+            sell_tax: tax_range(&data.sell_tax),
+        }
+    }
+}
+
+fn flag(value: &str) -> Option<bool> {
+    match value {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Either bound stands in for the other when DexTools only reports one.
+fn tax_range(tax: &Tax) -> Option<TaxRange> {
+    match (tax.min, tax.max) {
+        (Some(min), Some(max)) => Some(TaxRange { min, max }),
+        (Some(tax), None) | (None, Some(tax)) => Some(TaxRange { min: tax, max: tax }),
+        (None, None) => None,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OverviewLinks {
+    pub dexscreener: String,
+    pub ape_express: String,
+    pub explorer_address: String,
+    pub explorer_token: String,
+    pub twitter_search: String,
+}
+
+/// Computes the overview report for a completed scan.
+pub fn analyze(scan: &ScanData, links: &LinkConfig) -> TokenOverview {
+    let metrics = OverviewMetrics::from_scan(scan);
+    let decimals = metrics.decimals;
+    let address = &scan.token_info.address;
+
+    let top_holders = scan
+        .holders
+        .iter()
+        .flat_map(|holders| holders.list.iter().take(TOP_HOLDERS_LIMIT))
+        .map(|holder| {
+            let usd_amount = metrics
+                .price_usd
+                .map(|price| holder.balance.to_f64(decimals) * price);
+            HolderEntry {
+                address: holder.address.clone(),
+                balance: holder.balance.format_units(decimals),
+                percent_of_supply: holder.balance.percent_of(&scan.token_info.total_supply),
+                usd_amount,
+                tier: usd_amount.map(HolderTier::from_usd),
+                explorer_link: links.explorer_address(&holder.address),
+            }
+        })
+        .collect();
+
+    let valid_link = |link: &Option<String>| link.as_deref().and_then(safe_url);
+    let socials = scan
+        .token_info
+        .details
+        .as_ref()
+        .map(|details| Socials {
+            discord: valid_link(&details.discord),
+            telegram: valid_link(&details.telegram),
+            twitter: valid_link(&details.twitter),
+            website: valid_link(&details.website),
+        })
+        .unwrap_or_default();
+
+    let liquidity = scan
+        .token_info
+        .liquidity
+        .as_ref()
+        .map(|liquidity| LiquidityReport {
+            pair: liquidity.pair.clone(),
+            pair_link: links.explorer_address(&liquidity.pair),
+            router: liquidity.router.clone(),
+            native_reserve: liquidity.native_reserve.to_f64(NATIVE_DECIMALS),
+            token_reserve: liquidity.token_reserve.to_f64(decimals),
+            initial_native_reserve: liquidity.initial_native_reserve.to_f64(NATIVE_DECIMALS),
+            market_cap_ratio: metrics
+                .liquidity_usd
+                .zip(metrics.market_cap_usd)
+                .filter(|(_, market_cap)| *market_cap > 0.0)
+                .map(|(liquidity, market_cap)| liquidity / market_cap * 100.0),
+        });
+
+    let audit = scan
+        .audit
+        .as_ref()
+        .filter(|audit| audit.status_code == 200)
+        .and_then(|audit| audit.data.as_ref())
+        .map(AuditReport::from_audit);
+
+    let risk = RiskAssessment::assess(&metrics, audit.as_ref());
+
+    let links = OverviewLinks {
+        dexscreener: links.dexscreener(address),
+        ape_express: links.ape_express(address),
+        explorer_address: links.explorer_address(address),
+        explorer_token: links.explorer_token(address),
+        twitter_search: links.twitter_search(address),
+    };
+
+    TokenOverview {
+        metrics,
+        risk,
+        top_holders,
+        socials,
+        liquidity,
+        audit,
+        links,
+    }
+}
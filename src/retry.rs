@@ -0,0 +1,143 @@
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use log::debug;
+
+use crate::client::FetchError;
+use crate::config::RetryConfig;
+
+/// Runs `request` until it succeeds, fails permanently, or `config.max_attempts`
+/// is reached. Waits between attempts with exponential backoff and full
+/// jitter, or for the upstream's `Retry-After` when it sent one.
+pub async fn with_retry<T, F, Fut>(config: &RetryConfig, mut request: F) -> Result<T, FetchError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FetchError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match request().await {
+            Err(e) if e.is_transient() && attempt < config.max_attempts => {
+                let delay = match e {
+                    FetchError::RateLimited {
+                        retry_after: Some(after),
+                    } => after,
+                    _ => backoff(config, attempt),
+                };
+                debug!(
+                    "Attempt {} failed ({}), retrying in {}ms",
+                    attempt,
+                    e,
+                    delay.as_millis()
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Full-jitter exponential backoff: a random delay in
+/// `[0, exponential_delay(config, attempt)]`.
+fn backoff(config: &RetryConfig, attempt: u32) -> Duration {
+    exponential_delay(config, attempt).mul_f64(random_fraction())
+}
+
+/// `base_delay * 2^(attempt - 1)`, capped at `max_delay`.
+fn exponential_delay(config: &RetryConfig, attempt: u32) -> Duration {
+    config
+        .base_delay
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(config.max_delay)
+}
+
+/// A random number in `[0, 1]`, good enough to spread retries out but not
+/// for anything that needs real randomness. Every `RandomState` is seeded
+/// with fresh random keys, so hashing nothing with one yields a random
+/// `u64` without pulling in a random number crate.
+fn random_fraction() -> f64 {
+    RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tokio::time::Instant;
+
+    use super::*;
+
+    fn config() -> RetryConfig {
+        RetryConfig {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_millis(500),
+        }
+    }
+
+    #[test]
+    fn exponential_delay_doubles_up_to_the_cap() {
+        let config = config();
+        assert_eq!(exponential_delay(&config, 1), Duration::from_millis(200));
+        assert_eq!(exponential_delay(&config, 2), Duration::from_millis(400));
+        assert_eq!(exponential_delay(&config, 3), Duration::from_millis(500));
+        assert_eq!(exponential_delay(&config, 40), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn backoff_stays_within_the_cap() {
+        let config = config();
+        for attempt in 1..10 {
+            assert!(backoff(&config, attempt) <= exponential_delay(&config, attempt));
+        }
+        assert!((0.0..=1.0).contains(&random_fraction()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_for_retry_after() {
+        let calls = AtomicU32::new(0);
+        let start = Instant::now();
+        let result = with_retry(&config(), || async {
+            if calls.fetch_add(1, Ordering::Relaxed) == 0 {
+                Err(FetchError::RateLimited {
+                    retry_after: Some(Duration::from_secs(7)),
+                })
+            } else {
+                Ok(())
+            }
+        })
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+        assert_eq!(start.elapsed(), Duration::from_secs(7));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let start = Instant::now();
+        let result: Result<(), _> = with_retry(&config(), || async {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Err(FetchError::Timeout)
+        })
+        .await;
+        assert!(matches!(result, Err(FetchError::Timeout)));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert!(start.elapsed() <= Duration::from_millis(200 + 400));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn does_not_retry_permanent_failures() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = with_retry(&config(), || async {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Err(FetchError::NotFound)
+        })
+        .await;
+        assert!(matches!(result, Err(FetchError::NotFound)));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+}
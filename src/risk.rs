@@ -0,0 +1,340 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::metrics::OverviewMetrics;
+use crate::report::AuditReport;
+
+/// Scores above this are [`RiskLevel::High`].
+const HIGH_RISK_SCORE: u32 = 60;
+/// Scores above this are [`RiskLevel::Medium`].
+const MEDIUM_RISK_SCORE: u32 = 30;
+
+/// A 0–100 risk score, higher meaning riskier, with the warnings that make
+/// it up. Every warning adds its penalty; the total is capped at 100.
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskAssessment {
+    pub score: u32,
+    pub level: RiskLevel,
+    /// Triggered warnings, most severe first.
+    pub warnings: Vec<RiskWarning>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskWarning {
+    pub penalty: u32,
+    pub message: String,
+}
+
+impl fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskLevel::Low => write!(f, "low"),
+            RiskLevel::Medium => write!(f, "medium"),
+            RiskLevel::High => write!(f, "high"),
+        }
+    }
+}
+
+impl RiskAssessment {
+    /// Scores a token from its metrics and, when available, its audit.
+    /// Inputs that are unknown add no penalty, except a missing audit.
+    pub fn assess(metrics: &OverviewMetrics, audit: Option<&AuditReport>) -> Self {
+        let mut warnings = Vec::new();
+        let mut warn =
+            |penalty: u32, message: String| warnings.push(RiskWarning { penalty, message });
+
+        match audit {
+            Some(audit) => {
+                for (flag, penalty, message) in [
+                    (audit.honeypot, 100, "Honeypot: the token cannot be sold"),
+                    (audit.potentially_scam, 50, "Flagged as a potential scam"),
+                    (audit.blacklisted, 20, "The contract can blacklist holders"),
+                    (audit.mintable, 15, "The owner can mint new tokens"),
+                    (audit.proxy, 10, "Upgradeable proxy contract"),
+                    (
+                        audit.slippage_modifiable,
+                        10,
+                        "The owner can change the taxes",
+                    ),
+                ] {
+                    if flag == Some(true) {
+                        warn(penalty, message.to_string());
+                    }
+                }
+                if audit.open_source == Some(false) {
+                    warn(15, "Contract source is not verified".to_string());
+                }
+                if audit.contract_renounced == Some(false) {
+                    warn(5, "Contract ownership is not renounced".to_string());
+                }
+                for (label, tax) in [("Buy", audit.buy_tax), ("Sell", audit.sell_tax)] {
+                    let Some(tax) = tax else { continue };
+                    let penalty = match tax.max {
+                        max if max > 10.0 => 15,
+                        max if max > 5.0 => 5,
+                        _ => continue,
+                    };
+                    warn(penalty, format!("{label} tax up to {:.1}%", tax.max));
+                }
+            }
+            None => warn(10, "No security audit available".to_string()),
+        }
+
+        if let Some(holders) = &metrics.holders {
+            let penalty = match holders.top_10_percent {
+                share if share > 50.0 => 20,
+                share if share > 30.0 => 10,
+                _ => 0,
+            };
+            if penalty > 0 {
+                warn(
+                    penalty,
+                    format!(
+                        "Top 10 holders own {:.1}% of the supply",
+                        holders.top_10_percent
+                    ),
+                );
+            }
+            if let Some(creator_percent) = holders.creator_percent {
+                let penalty = match creator_percent {
+                    share if share > 10.0 => 15,
+                    share if share > 5.0 => 5,
+                    _ => 0,
+                };
+                if penalty > 0 {
+                    warn(
+                        penalty,
+                        format!("Creator holds {creator_percent:.1}% of the supply"),
+                    );
+                }
+            }
+        }
+
+        if let (Some(liquidity), Some(market_cap)) = (metrics.liquidity_usd, metrics.market_cap_usd)
+        {
+            if market_cap > 0.0 {
+                let ratio = liquidity / market_cap * 100.0;
+                let penalty = match ratio {
+                    ratio if ratio < 2.0 => 20,
+                    ratio if ratio < 5.0 => 10,
+                    _ => 0,
+                };
+                if penalty > 0 {
+                    warn(
+                        penalty,
+                        format!("Liquidity is only {ratio:.1}% of the market cap"),
+                    );
+                }
+            }
+        }
+
+        match metrics.age_days {
+            Some(days) if days < 1 => warn(15, "Launched less than a day ago".to_string()),
+            Some(days) if days < 7 => warn(5, format!("Launched {days} days ago")),
+            _ => {}
+        }
+
+        warnings.sort_by_key(|warning| std::cmp::Reverse(warning.penalty));
+        let score = warnings
+            .iter()
+            .map(|warning| warning.penalty)
+            .sum::<u32>()
+            .min(100);
+        let level = if score > HIGH_RISK_SCORE {
+            RiskLevel::High
+        } else if score > MEDIUM_RISK_SCORE {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        };
+
+        Self {
+            score,
+            level,
+            warnings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::HolderMetrics;
+    use crate::report::TaxRange;
+
+    /// An established token with a healthy pool and spread-out holders.
+    fn metrics() -> OverviewMetrics {
+        OverviewMetrics {
+            address: "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".to_string(),
+            name: "Test".to_string(),
+            symbol: "TEST".to_string(),
+            decimals: 18,
+            decimals_assumed: false,
+            total_supply: "1000000000".to_string(),
+            native_price_usd: Some(1.0),
+            native_price_stale: false,
+            native_price_age_secs: Some(0),
+            price_native: Some(0.001),
+            price_usd: Some(0.001),
+            market_cap_usd: Some(1_000_000.0),
+            liquidity_usd: Some(200_000.0),
+            created_at: None,
+            age_days: Some(30),
+            price_history: None,
+            holders: Some(HolderMetrics {
+                total: 500,
+                top_10_percent: 20.0,
+                creator_percent: Some(1.0),
+                tiers: None,
+            }),
+        }
+    }
+
+    /// An audit with nothing to flag.
+    fn audit() -> AuditReport {
+        AuditReport {
+            open_source: Some(true),
+            honeypot: Some(false),
+            mintable: Some(false),
+            proxy: Some(false),
+            slippage_modifiable: Some(false),
+            blacklisted: Some(false),
+            contract_renounced: Some(true),
+            potentially_scam: Some(false),
+            buy_tax: Some(TaxRange { min: 0.0, max: 0.0 }),
+            sell_tax: Some(TaxRange { min: 0.0, max: 0.0 }),
+        }
+    }
+
+    fn penalties(assessment: &RiskAssessment) -> Vec<u32> {
+        assessment
+            .warnings
+            .iter()
+            .map(|warning| warning.penalty)
+            .collect()
+    }
+
+    #[test]
+    fn clean_token_is_low_risk() {
+        let assessment = RiskAssessment::assess(&metrics(), Some(&audit()));
+        assert_eq!(assessment.score, 0);
+        assert_eq!(assessment.level, RiskLevel::Low);
+        assert!(assessment.warnings.is_empty());
+    }
+
+    #[test]
+    fn honeypot_caps_the_score_at_100() {
+        let audit = AuditReport {
+            honeypot: Some(true),
+            mintable: Some(true),
+            ..audit()
+        };
+        let assessment = RiskAssessment::assess(&metrics(), Some(&audit));
+        assert_eq!(assessment.score, 100);
+        assert_eq!(assessment.level, RiskLevel::High);
+        assert_eq!(penalties(&assessment), vec![100, 15]);
+    }
+
+    #[test]
+    fn taxes_are_penalized_above_5_and_10_percent() {
+        for (max, expected) in [
+            (5.0, vec![]),
+            (7.5, vec![5]),
+            (10.0, vec![5]),
+            (12.0, vec![15]),
+        ] {
+            let audit = AuditReport {
+                sell_tax: Some(TaxRange { min: 0.0, max }),
+                ..audit()
+            };
+            let assessment = RiskAssessment::assess(&metrics(), Some(&audit));
+            assert_eq!(penalties(&assessment), expected, "sell tax {max}");
+        }
+        let audit = AuditReport {
+            buy_tax: Some(TaxRange {
+                min: 0.0,
+                max: 20.0,
+            }),
+            sell_tax: Some(TaxRange { min: 0.0, max: 8.0 }),
+            ..audit()
+        };
+        let assessment = RiskAssessment::assess(&metrics(), Some(&audit));
+        assert_eq!(assessment.score, 20);
+        assert_eq!(assessment.warnings[0].message, "Buy tax up to 20.0%");
+        assert_eq!(assessment.warnings[1].message, "Sell tax up to 8.0%");
+    }
+
+    #[test]
+    fn concentrated_top_holders_are_penalized() {
+        for (top_10_percent, expected) in [(30.0, vec![]), (40.0, vec![10]), (60.0, vec![20])] {
+            let mut metrics = metrics();
+            metrics.holders.as_mut().unwrap().top_10_percent = top_10_percent;
+            let assessment = RiskAssessment::assess(&metrics, Some(&audit()));
+            assert_eq!(penalties(&assessment), expected, "top 10 {top_10_percent}%");
+        }
+    }
+
+    #[test]
+    fn creator_holdings_are_penalized() {
+        for (creator_percent, expected) in [
+            (None, vec![]),
+            (Some(5.0), vec![]),
+            (Some(8.0), vec![5]),
+            (Some(15.0), vec![15]),
+        ] {
+            let mut metrics = metrics();
+            metrics.holders.as_mut().unwrap().creator_percent = creator_percent;
+            let assessment = RiskAssessment::assess(&metrics, Some(&audit()));
+            assert_eq!(
+                penalties(&assessment),
+                expected,
+                "creator {creator_percent:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_audit_is_penalized() {
+        let assessment = RiskAssessment::assess(&metrics(), None);
+        assert_eq!(assessment.score, 10);
+        assert_eq!(
+            assessment.warnings[0].message,
+            "No security audit available"
+        );
+    }
+
+    #[test]
+    fn token_without_a_pool_has_no_liquidity_penalty() {
+        let metrics = OverviewMetrics {
+            liquidity_usd: None,
+            ..metrics()
+        };
+        let assessment = RiskAssessment::assess(&metrics, Some(&audit()));
+        assert!(assessment.warnings.is_empty());
+    }
+
+    #[test]
+    fn thin_liquidity_is_penalized() {
+        for (liquidity_usd, expected) in [(10_000.0, vec![20]), (30_000.0, vec![10])] {
+            let metrics = OverviewMetrics {
+                liquidity_usd: Some(liquidity_usd),
+                ..metrics()
+            };
+            let assessment = RiskAssessment::assess(&metrics, Some(&audit()));
+            assert_eq!(
+                penalties(&assessment),
+                expected,
+                "liquidity ${liquidity_usd}"
+            );
+        }
+    }
+}
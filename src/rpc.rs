@@ -0,0 +1,41 @@
+use serde::Deserialize;
+
+use crate::token_amount::TokenAmount;
+
+/// A JSON-RPC 2.0 response. Exactly one of `result` and `error` is set,
+/// except that `result` is `null` for lookups that found nothing.
+#[derive(Debug, Deserialize)]
+pub struct RpcResponse<T> {
+    pub result: Option<T>,
+    pub error: Option<RpcError>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// `eth_getTransactionByHash`. Quantities are `0x` hex on the wire.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcTransaction {
+    pub hash: String,
+    pub from: String,
+    /// `None` for contract creations.
+    pub to: Option<String>,
+    pub value: TokenAmount,
+    /// `None` while the transaction is pending.
+    #[serde(rename = "blockNumber")]
+    pub block_number: Option<TokenAmount>,
+}
+
+/// `eth_getTransactionReceipt`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcReceipt {
+    /// `0x1` on success, `0x0` when the transaction reverted.
+    pub status: Option<String>,
+    #[serde(rename = "gasUsed")]
+    pub gas_used: TokenAmount,
+    #[serde(rename = "contractAddress")]
+    pub contract_address: Option<String>,
+}
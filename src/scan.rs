@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+use log::{debug, warn};
+use reqwest::Client;
+use tokio::sync::Semaphore;
+
+use crate::address::{Address, TxHash};
+use crate::cache::ScanCache;
+use crate::client::{ApeExpressClient, DexToolsClient, FetchError, RpcClient};
+use crate::config::{Config, NativePriceConfig, Timeouts};
+use crate::price_feed::{NativePriceFeed, NativePriceStatus};
+use crate::rpc::{RpcReceipt, RpcTransaction};
+use crate::token_amount::TokenAmount;
+use crate::token_audit::TokenAudit;
+use crate::token_holders::TokenTopHolders;
+use crate::token_info::TokenInfo;
+use crate::token_overview::TokenOverview;
+use crate::token_price_history::TokenPriceHistory;
+use crate::wallet_holdings::WalletHoldings;
+
+/// Decimals assumed when DexTools cannot tell us the token's own.
+pub const DEFAULT_TOKEN_DECIMALS: u32 = 18;
+
+/// DexTools requests made by each scan in a [`Scanner::scan_many`] batch:
+/// the token overview and the price history.
+const DEXTOOLS_CALLS_PER_BATCH_SCAN: f64 = 2.0;
+
+/// Everything fetched for one token overview. Only `token_info` is required;
+/// the other sections are `None` when their upstream failed or timed out.
+/// The native price comes from the background feed rather than a fetch.
+#[derive(Debug, Clone)]
+pub struct ScanData {
+    pub token_info: TokenInfo,
+    pub token_overview: Option<TokenOverview>,
+    pub price_history: Option<TokenPriceHistory>,
+    pub holders: Option<TokenTopHolders>,
+    pub audit: Option<TokenAudit>,
+    pub native_price: NativePriceStatus,
+}
+
+impl ScanData {
+    /// The token's decimals as reported by DexTools, or
+    /// [`DEFAULT_TOKEN_DECIMALS`] when unknown.
+    pub fn decimals(&self) -> u32 {
+        self.reported_decimals().unwrap_or(DEFAULT_TOKEN_DECIMALS)
+    }
+
+    /// Whether [`ScanData::decimals`] fell back to
+    /// [`DEFAULT_TOKEN_DECIMALS`] because DexTools did not report them.
+    pub fn decimals_assumed(&self) -> bool {
+        self.reported_decimals().is_none()
+    }
+
+    fn reported_decimals(&self) -> Option<u32> {
+        self.token_overview
+            .as_ref()
+            .and_then(|overview| valid_decimals(overview.data.decimals))
+    }
+}
+
+/// How many scans a batch runs at once. Their DexTools calls queue at the
+/// shared rate limiter, and that wait counts against the section timeouts,
+/// so only as many scans run as the limiter lets send every call within the
+/// shorter of the two DexTools timeouts. On the free plan (1 request per
+/// second, 4 s timeouts) that is 2. Never below 1.
+fn batch_concurrency(config: &Config) -> usize {
+    let window = config
+        .timeouts
+        .token_overview
+        .min(config.timeouts.price_history)
+        .as_secs_f64();
+    let calls = (config.dextools_rate_limit * window).floor();
+    ((calls / DEXTOOLS_CALLS_PER_BATCH_SCAN) as usize).max(1)
+}
+
+/// `decimals` if a U256 amount can have that many, which rules out
+/// negative and garbage values.
+fn valid_decimals(decimals: i32) -> Option<u32> {
+    u32::try_from(decimals)
+        .ok()
+        .filter(|decimals| *decimals <= 77)
+}
+
+/// What an address pasted into the chat turned out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    /// A token ape.express knows.
+    Token,
+    /// A DEX pool; `token` is the token it trades.
+    Pair { token: Address },
+    /// An externally owned account.
+    Wallet,
+    /// Any other contract.
+    Contract,
+}
+
+/// Why an address in a batch has no scan.
+#[derive(Debug, Clone)]
+pub enum NotScanned {
+    /// The address is a wallet, pool or other contract rather than a token.
+    NotToken(AddressKind),
+    Fetch(FetchError),
+}
+
+/// On-chain state of a wallet and what ape.express knows about it.
+#[derive(Debug, Clone)]
+pub struct WalletData {
+    pub address: Address,
+    pub balance: TokenAmount,
+    pub transaction_count: u64,
+    /// `None` when ape.express failed or timed out; empty for wallets it
+    /// has never seen.
+    pub holdings: Option<WalletHoldings>,
+    /// Decimals of the held tokens, by lowercase token address, taken from
+    /// the holdings themselves or from cached DexTools overviews. Tokens
+    /// missing here have unknown decimals.
+    pub token_decimals: HashMap<String, u32>,
+    pub native_price: NativePriceStatus,
+}
+
+/// A transaction and, once it is mined, its receipt.
+#[derive(Debug, Clone)]
+pub struct TransactionData {
+    pub transaction: RpcTransaction,
+    pub receipt: Option<RpcReceipt>,
+    pub native_price: NativePriceStatus,
+}
+
+/// The fetch pipeline behind every token overview: upstream clients, their
+/// response cache and the shared native price.
+#[derive(Debug)]
+pub struct Scanner {
+    pub ape_express: ApeExpressClient,
+    pub dextools: DexToolsClient,
+    pub rpc: RpcClient,
+    pub cache: ScanCache,
+    pub native_price: Arc<NativePriceFeed>,
+    pub timeouts: Timeouts,
+    /// Most addresses [`Scanner::scan_many`] works on at once; see
+    /// [`batch_concurrency`].
+    pub batch_concurrency: usize,
+}
+
+impl Scanner {
+    pub fn new(
+        ape_express: ApeExpressClient,
+        dextools: DexToolsClient,
+        rpc: RpcClient,
+        native_price: Arc<NativePriceFeed>,
+        config: &Config,
+    ) -> Self {
+        Self {
+            ape_express,
+            dextools,
+            rpc,
+            cache: ScanCache::new(&config.cache_ttls),
+            native_price,
+            timeouts: config.timeouts.clone(),
+            batch_concurrency: batch_concurrency(config),
+        }
+    }
+
+    /// Builds the clients and native price feed described by `config`.
+    /// The feed starts empty; call [`Scanner::spawn_native_price_refresher`]
+    /// or [`NativePriceFeed::refresh`] to populate it.
+    pub fn from_config(config: &Config) -> Self {
+        let client = Client::new();
+        Self::new(
+            ApeExpressClient::new(client.clone(), config),
+            DexToolsClient::new(client.clone(), config),
+            RpcClient::new(client, config),
+            Arc::new(NativePriceFeed::new(config.native_price.max_age)),
+            config,
+        )
+    }
+
+    /// Keeps the native price current in the background. Must be called from
+    /// within a Tokio runtime.
+    pub fn spawn_native_price_refresher(&self, config: &NativePriceConfig) {
+        self.native_price
+            .clone()
+            .spawn_refresher(self.ape_express.clone(), config.clone());
+    }
+
+    /// Fetches every section of the overview concurrently, each bounded by
+    /// its own timeout and served from the cache while fresh. Fails only when
+    /// the token info itself cannot be fetched.
+    pub async fn scan(&self, token_address: &Address) -> Result<ScanData, FetchError> {
+        self.scan_sections(token_address, true).await
+    }
+
+    /// Like [`Scanner::scan`], but leaves out the audit when `with_audit`
+    /// is false.
+    async fn scan_sections(
+        &self,
+        token_address: &Address,
+        with_audit: bool,
+    ) -> Result<ScanData, FetchError> {
+        let key = token_address.to_string();
+        let (token_info, token_overview, price_history, holders, audit) = tokio::join!(
+            self.cache.token_info.get_or_try_insert_with(
+                &key,
+                with_timeout(
+                    self.timeouts.token_info,
+                    self.ape_express.token_info(token_address)
+                ),
+            ),
+            self.cache.token_overview.get_or_try_insert_with(
+                &key,
+                with_timeout(
+                    self.timeouts.token_overview,
+                    self.dextools.token_overview(token_address)
+                ),
+            ),
+            self.cache.price_history.get_or_try_insert_with(
+                &key,
+                with_timeout(
+                    self.timeouts.price_history,
+                    self.dextools.price_history(token_address)
+                ),
+            ),
+            self.cache.holders.get_or_try_insert_with(
+                &key,
+                with_timeout(
+                    self.timeouts.holders,
+                    self.ape_express.holders(token_address)
+                ),
+            ),
+            async {
+                if !with_audit {
+                    return None;
+                }
+                let audit = self.cache.audit.get_or_try_insert_with(
+                    &key,
+                    with_timeout(self.timeouts.audit, self.dextools.audit(token_address)),
+                );
+                Some(audit.await)
+            },
+        );
+        debug!("Scan cache stats: {}", self.cache);
+
+        Ok(ScanData {
+            token_info: token_info?,
+            token_overview: section("token overview", token_overview),
+            price_history: section("price history", price_history),
+            holders: section("holders", holders),
+            audit: audit.and_then(|audit| section("audit", audit)),
+            native_price: self.native_price.status(),
+        })
+    }
+
+    /// Classifies and scans several addresses, at most
+    /// [`Scanner::batch_concurrency`] at a time, for a comparison table. Scans
+    /// leave out the audit, which the table does not show. Results are in
+    /// the same order as `addresses`.
+    pub async fn scan_many(&self, addresses: &[Address]) -> Vec<Result<ScanData, NotScanned>> {
+        let permits = Semaphore::new(self.batch_concurrency);
+        join_all(
+            addresses
+                .iter()
+                .map(|address| self.scan_batch_entry(address, &permits)),
+        )
+        .await
+    }
+
+    async fn scan_batch_entry(
+        &self,
+        address: &Address,
+        permits: &Semaphore,
+    ) -> Result<ScanData, NotScanned> {
+        let _permit = permits.acquire().await;
+        match self.classify(address).await {
+            Ok(AddressKind::Token) => self
+                .scan_sections(address, false)
+                .await
+                .map_err(NotScanned::Fetch),
+            Ok(kind) => Err(NotScanned::NotToken(kind)),
+            Err(e) => Err(NotScanned::Fetch(e)),
+        }
+    }
+
+    /// Works out what `address` is: an ape.express token first, then a
+    /// wallet if it has no code, then a pool if DexTools knows one there.
+    pub async fn classify(&self, address: &Address) -> Result<AddressKind, FetchError> {
+        let token_info = self
+            .cache
+            .token_info
+            .get_or_try_insert_with(
+                &address.to_string(),
+                with_timeout(
+                    self.timeouts.token_info,
+                    self.ape_express.token_info(address),
+                ),
+            )
+            .await;
+        match token_info {
+            Ok(_) => return Ok(AddressKind::Token),
+            Err(FetchError::NotFound) => {}
+            Err(FetchError::Decode(e)) => {
+                warn!(
+                    "Token info for {} did not decode, trying other kinds: {}",
+                    address, e
+                )
+            }
+            Err(e) => return Err(e),
+        }
+
+        let code = with_timeout(self.timeouts.rpc, self.rpc.code(address)).await?;
+        if code.trim_start_matches("0x").is_empty() {
+            return Ok(AddressKind::Wallet);
+        }
+
+        let pool = with_timeout(self.timeouts.token_overview, self.dextools.pool(address)).await;
+        let token = match pool {
+            Ok(pool) => pool
+                .data
+                .and_then(|data| data.main_token.address.parse().ok()),
+            Err(e) if e.is_not_found() => None,
+            Err(e) => {
+                warn!("Error fetching pool {}: {}", address, e);
+                None
+            }
+        };
+        Ok(match token {
+            Some(token) => AddressKind::Pair { token },
+            None => AddressKind::Contract,
+        })
+    }
+
+    /// Fetches a wallet's balances and its ape.express holdings
+    /// concurrently. Fails only when the node cannot be reached.
+    pub async fn wallet(&self, address: &Address) -> Result<WalletData, FetchError> {
+        let key = address.to_string();
+        let (balance, transaction_count, holdings) = tokio::join!(
+            with_timeout(self.timeouts.rpc, self.rpc.balance(address)),
+            with_timeout(self.timeouts.rpc, self.rpc.transaction_count(address)),
+            self.cache.wallet_holdings.get_or_try_insert_with(
+                &key,
+                with_timeout(
+                    self.timeouts.wallet_holdings,
+                    self.ape_express.wallet_holdings(address)
+                ),
+            ),
+        );
+        let holdings = match holdings {
+            Err(FetchError::NotFound) => Ok(WalletHoldings::default()),
+            holdings => holdings,
+        };
+        let holdings = section("wallet holdings", holdings);
+        let token_decimals = holdings
+            .iter()
+            .flat_map(|holdings| &holdings.holdings)
+            .filter_map(|holding| {
+                let token = &holding.token;
+                let decimals = token.decimals.and_then(valid_decimals).or_else(|| {
+                    self.cache
+                        .token_overview
+                        .get(&token.address)
+                        .and_then(|overview| valid_decimals(overview.data.decimals))
+                })?;
+                Some((token.address.to_lowercase(), decimals))
+            })
+            .collect();
+        Ok(WalletData {
+            address: *address,
+            balance: balance?,
+            transaction_count: transaction_count?,
+            holdings,
+            token_decimals,
+            native_price: self.native_price.status(),
+        })
+    }
+
+    /// Fails with [`FetchError::NotFound`] when the node has never seen
+    /// the transaction.
+    pub async fn transaction(&self, hash: &TxHash) -> Result<TransactionData, FetchError> {
+        let (transaction, receipt) = tokio::join!(
+            with_timeout(self.timeouts.rpc, self.rpc.transaction(hash)),
+            with_timeout(self.timeouts.rpc, self.rpc.receipt(hash)),
+        );
+        Ok(TransactionData {
+            transaction: transaction?,
+            receipt: receipt?,
+            native_price: self.native_price.status(),
+        })
+    }
+}
+
+async fn with_timeout<T>(
+    duration: Duration,
+    fetch: impl Future<Output = Result<T, FetchError>>,
+) -> Result<T, FetchError> {
+    tokio::time::timeout(duration, fetch)
+        .await
+        .unwrap_or(Err(FetchError::Timeout))
+}
+
+fn section<T>(name: &str, result: Result<T, FetchError>) -> Option<T> {
+    result
+        .map_err(|e| warn!("Error fetching {}: {}", name, e))
+        .ok()
+}
@@ -0,0 +1,188 @@
+use crate::address::checksummed;
+use crate::html::{self, escape};
+use crate::overview::{controll_big_float, num_floating_point, short_address};
+use crate::render::{
+    assumed_decimals_html, audit_lines_html, holders_map, price_history_html, risk_section_html,
+    tier_symbol, HoldersMapLayout, TELEGRAM_MESSAGE_LIMIT,
+};
+use crate::report::TokenOverview;
+
+/// How many holders the holders section lists individually.
+const LISTED_HOLDERS: usize = 20;
+
+/// One part of the overview, shown on its own and in more detail by the
+/// bot's section commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Price,
+    Holders,
+    Audit,
+    Liquidity,
+    Socials,
+    Risk,
+}
+
+/// Renders one section as Telegram HTML, headed by the token's name and
+/// followed by its address.
+pub fn section_html(overview: &TokenOverview, section: Section) -> String {
+    let metrics = &overview.metrics;
+    let body = match section {
+        Section::Price => price_html(overview),
+        Section::Holders => holders_html(overview),
+        Section::Audit => audit_html(overview),
+        Section::Liquidity => liquidity_html(overview),
+        Section::Socials => socials_html(overview),
+        Section::Risk => risk_section_html(&overview.risk),
+    };
+    format!(
+        "🚀 {}  ${}\n{body}<code>{}</code>",
+        escape(&metrics.name),
+        escape(&metrics.symbol),
+        escape(&checksummed(&metrics.address))
+    )
+}
+
+/// [`section_html`] split into messages within [`TELEGRAM_MESSAGE_LIMIT`].
+pub fn section_messages(overview: &TokenOverview, section: Section) -> Vec<String> {
+    html::split(&section_html(overview, section), TELEGRAM_MESSAGE_LIMIT)
+}
+
+fn usd(value: Option<f64>) -> String {
+    match value {
+        Some(value) => format!("${}", controll_big_float(value)),
+        None => "unavailable".to_string(),
+    }
+}
+
+fn price_html(overview: &TokenOverview) -> String {
+    let metrics = &overview.metrics;
+    let mut text = String::from("💰 Price\n");
+    match metrics.price_usd {
+        Some(price) => text += &format!("        └ USD:  ${}\n", num_floating_point(&price, 5)),
+        None => text += "        └ USD:  unavailable\n",
+    }
+    if let Some(price) = metrics.price_native {
+        text += &format!("        └ APE:  {price} APE\n");
+    }
+    text += &format!("        └ Mcap:  {}\n", usd(metrics.market_cap_usd));
+    match metrics.native_price_usd {
+        Some(native) => {
+            text += &format!("        └ APE/USD:  ${native:.4}\n");
+            if metrics.native_price_stale {
+                let minutes = metrics.native_price_age_secs.unwrap_or_default() / 60;
+                text += &format!("⚠️ <i>APE price stale, last updated {minutes} min ago</i>\n");
+            }
+        }
+        None => text += "⚠️ <i>APE price unavailable</i>\n",
+    }
+    if let Some(notice) = assumed_decimals_html(metrics) {
+        text += &notice;
+        text += "\n";
+    }
+    text += &price_history_html(metrics);
+    text += "\n";
+    text
+}
+
+fn holders_html(overview: &TokenOverview) -> String {
+    let mut text = String::from("👩‍👧‍👦 Holders\n");
+    let Some(holders) = &overview.metrics.holders else {
+        return text + "        └ <i>temporarily unavailable</i>\n";
+    };
+    text += &format!("        └ Holders:  {}\n", holders.total);
+    text += &format!("        └ Top 10:  {:.2}%\n", holders.top_10_percent);
+    if let Some(creator_percent) = holders.creator_percent {
+        text += &format!("        └ Creator:  {creator_percent:.2}%\n");
+    }
+
+    for (rank, holder) in overview.top_holders.iter().take(LISTED_HOLDERS).enumerate() {
+        let link = escape(&holder.explorer_link);
+        let address = escape(&short_address(&holder.address));
+        let tier = holder.tier.map(tier_symbol).unwrap_or_default();
+        text += &format!(
+            "{}. <a href=\"{link}\">{address}</a>  {:.2}%  {} {tier}\n",
+            rank + 1,
+            holder.percent_of_supply,
+            usd(holder.usd_amount)
+        );
+    }
+    if let Some(tiers) = &holders.tiers {
+        text += &holders_map(overview, tiers, HoldersMapLayout::Compact);
+    }
+    text
+}
+
+fn audit_html(overview: &TokenOverview) -> String {
+    let lines = overview
+        .audit
+        .as_ref()
+        .map(audit_lines_html)
+        .unwrap_or_default();
+    if lines.is_empty() {
+        return "🔍 Audit\n        └ <i>No audit available</i>\n".to_string();
+    }
+    format!("🔍 Audit\n{lines}")
+}
+
+fn liquidity_html(overview: &TokenOverview) -> String {
+    let metrics = &overview.metrics;
+    let mut text = String::from("💦 Liquidity\n");
+    let Some(liquidity) = &overview.liquidity else {
+        return text + "        └ <i>No DEX pool yet</i>\n";
+    };
+    text += &format!("        └ USD:  {}\n", usd(metrics.liquidity_usd));
+    text += &format!(
+        "        └ APE reserve:  {} APE\n",
+        controll_big_float(liquidity.native_reserve)
+    );
+    text += &format!(
+        "        └ Token reserve:  {} {}\n",
+        controll_big_float(liquidity.token_reserve),
+        escape(&metrics.symbol)
+    );
+    text += &format!(
+        "        └ Initial APE:  {} APE\n",
+        controll_big_float(liquidity.initial_native_reserve)
+    );
+    if let Some(ratio) = liquidity.market_cap_ratio {
+        text += &format!("        └ Liquidity / Mcap:  {ratio:.2}%\n");
+    }
+    text += &format!(
+        "        └ Pair:  <a href=\"{}\">{}</a>\n",
+        escape(&liquidity.pair_link),
+        escape(&short_address(&liquidity.pair))
+    );
+    text
+}
+
+fn socials_html(overview: &TokenOverview) -> String {
+    let mut text = String::from("🧰 Socials\n");
+    let socials = &overview.socials;
+    let mut listed = false;
+    for (icon, label, link) in [
+        ("💭", "Discord", &socials.discord),
+        ("🕊️", "Telegram", &socials.telegram),
+        ("𝕏", "Twitter", &socials.twitter),
+        ("🌐", "Website", &socials.website),
+    ] {
+        if let Some(link) = link {
+            let link = escape(link);
+            text += &format!("        └ {icon} {label}:  <a href=\"{link}\">{link}</a>\n");
+            listed = true;
+        }
+    }
+    if !listed {
+        text += "        └ <i>No socials listed</i>\n";
+    }
+
+    let links = &overview.links;
+    for (label, link) in [
+        ("ape.express", &links.ape_express),
+        ("DEX Screener", &links.dexscreener),
+        ("APE Scan", &links.explorer_token),
+        ("Search on 𝕏", &links.twitter_search),
+    ] {
+        text += &format!("🔗 <a href=\"{}\">{label}</a>\n", escape(link));
+    }
+    text
+}
@@ -0,0 +1,244 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+
+use log::warn;
+use primitive_types::U256;
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// An exact on-chain token amount in base units (wei), as reported by the
+/// upstream APIs as a decimal string.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount(pub U256);
+
+impl TokenAmount {
+    pub const ZERO: TokenAmount = TokenAmount(U256::zero());
+
+    pub fn raw(&self) -> U256 {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Exact decimal representation with `decimals` fractional digits,
+    /// trailing zeros trimmed, e.g. `1500000000000000000` at 18 decimals is
+    /// `"1.5"`.
+    pub fn format_units(&self, decimals: u32) -> String {
+        let digits = self.0.to_string();
+        let decimals = decimals as usize;
+        if decimals == 0 {
+            return digits;
+        }
+
+        let padded = format!("{:0>width$}", digits, width = decimals + 1);
+        let (whole, fraction) = padded.split_at(padded.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            whole.to_string()
+        } else {
+            format!("{whole}.{fraction}")
+        }
+    }
+
+    /// The amount in whole tokens as a float, for multiplying with prices.
+    pub fn to_f64(&self, decimals: u32) -> f64 {
+        self.format_units(decimals).parse().unwrap_or_default()
+    }
+
+    /// This amount as a percentage of `total`, computed in integer space so
+    /// it does not lose precision for 18-decimal supplies.
+    pub fn percent_of(&self, total: &TokenAmount) -> f64 {
+        if total.is_zero() {
+            return 0.0;
+        }
+        // Six digits of precision after the integer percentage.
+        const SCALE: u64 = 100_000_000;
+        match self.0.checked_mul(U256::from(SCALE)) {
+            Some(scaled) => TokenAmount(scaled / total.0).to_f64(6),
+            None => self.to_f64(0) / total.to_f64(0) * 100.0,
+        }
+    }
+}
+
+impl From<u64> for TokenAmount {
+    fn from(value: u64) -> Self {
+        TokenAmount(U256::from(value))
+    }
+}
+
+impl Add for TokenAmount {
+    type Output = TokenAmount;
+
+    fn add(self, other: TokenAmount) -> TokenAmount {
+        TokenAmount(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sum for TokenAmount {
+    fn sum<I: Iterator<Item = TokenAmount>>(iter: I) -> Self {
+        iter.fold(TokenAmount::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a TokenAmount> for TokenAmount {
+    fn sum<I: Iterator<Item = &'a TokenAmount>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for TokenAmount {
+    type Err = String;
+
+    /// Parses a decimal integer string, or a `0x`-prefixed hex string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let parsed = match s.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).map_err(|e| e.to_string()),
+            None => U256::from_dec_str(s).map_err(|e| format!("{e:?}")),
+        };
+        parsed
+            .map(TokenAmount)
+            .map_err(|e| format!("invalid token amount {s:?}: {e}"))
+    }
+}
+
+impl Serialize for TokenAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TokenAmountVisitor;
+
+        impl Visitor<'_> for TokenAmountVisitor {
+            type Value = TokenAmount;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an unsigned integer or a decimal integer string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<TokenAmount, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<TokenAmount, E> {
+                Ok(TokenAmount::from(value))
+            }
+        }
+
+        deserializer.deserialize_any(TokenAmountVisitor)
+    }
+}
+
+/// Deserializes an amount nobody computes with, for `#[serde(default,
+/// deserialize_with = "...")]` on an `Option<TokenAmount>` field. A value
+/// that is not an exact integer (say `"1.5"` or `1e21`) becomes `None` with a
+/// warning instead of failing the whole response.
+pub fn deserialize_lenient<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<TokenAmount>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(
+        value.and_then(|value| match TokenAmount::deserialize(&value) {
+            Ok(amount) => Some(amount),
+            Err(e) => {
+                warn!("Ignoring malformed token amount {}: {}", value, e);
+                None
+            }
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(s: &str) -> TokenAmount {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_decimal_and_hex() {
+        assert_eq!(amount("1500"), TokenAmount::from(1500));
+        assert_eq!(amount(" 42 "), TokenAmount::from(42));
+        assert_eq!(amount("0x5dc"), TokenAmount::from(1500));
+        assert_eq!(amount("0x0"), TokenAmount::ZERO);
+    }
+
+    #[test]
+    fn rejects_non_integers_and_overflow() {
+        assert!("1.5".parse::<TokenAmount>().is_err());
+        assert!("1e21".parse::<TokenAmount>().is_err());
+        assert!("-1".parse::<TokenAmount>().is_err());
+        assert!("0xzz".parse::<TokenAmount>().is_err());
+        let max = U256::MAX.to_string();
+        assert_eq!(amount(&max), TokenAmount(U256::MAX));
+        assert!(format!("{max}0").parse::<TokenAmount>().is_err());
+        assert!(format!("0x1{}", "0".repeat(64))
+            .parse::<TokenAmount>()
+            .is_err());
+    }
+
+    #[test]
+    fn formats_units_trimming_trailing_zeros() {
+        assert_eq!(amount("1500000000000000000").format_units(18), "1.5");
+        assert_eq!(amount("1000000000000000000").format_units(18), "1");
+        assert_eq!(amount("1").format_units(18), "0.000000000000000001");
+        assert_eq!(TokenAmount::ZERO.format_units(18), "0");
+        assert_eq!(amount("1500").format_units(0), "1500");
+    }
+
+    #[test]
+    fn formats_units_beyond_u128() {
+        let large = format!("{}000000000000000000", u128::MAX);
+        assert_eq!(amount(&large).format_units(18), u128::MAX.to_string());
+        assert_eq!(
+            TokenAmount(U256::MAX).format_units(0),
+            U256::MAX.to_string()
+        );
+    }
+
+    #[test]
+    fn percent_of() {
+        assert_eq!(amount("25").percent_of(&amount("100")), 25.0);
+        assert_eq!(amount("1").percent_of(&amount("3")), 33.333333);
+        assert_eq!(amount("5").percent_of(&TokenAmount::ZERO), 0.0);
+        assert_eq!(
+            TokenAmount(U256::MAX).percent_of(&TokenAmount(U256::MAX)),
+            100.0
+        );
+    }
+
+    #[test]
+    fn deserializes_strings_hex_and_numbers() {
+        let parsed: Vec<TokenAmount> = serde_json::from_str(r#"["10", "0xa", 10]"#).unwrap();
+        assert_eq!(parsed, vec![TokenAmount::from(10); 3]);
+        assert!(serde_json::from_str::<TokenAmount>(r#""1.5""#).is_err());
+    }
+
+    #[test]
+    fn lenient_amounts_fall_back_to_none() {
+        #[derive(Deserialize)]
+        struct Lenient {
+            #[serde(default, deserialize_with = "deserialize_lenient")]
+            amount: Option<TokenAmount>,
+        }
+        let parse = |json: &str| serde_json::from_str::<Lenient>(json).unwrap().amount;
+        assert_eq!(parse(r#"{"amount": "7"}"#), Some(TokenAmount::from(7)));
+        assert_eq!(parse(r#"{"amount": "1.5"}"#), None);
+        assert_eq!(parse(r#"{"amount": 1e21}"#), None);
+        assert_eq!(parse(r#"{"amount": null}"#), None);
+        assert_eq!(parse("{}"), None);
+    }
+}
@@ -4,10 +4,14 @@ use serde::{Deserialize, Serialize};
 pub struct TokenAudit {
     #[serde(rename = "statusCode")]
     pub status_code: u32,
-    pub data: TokenAuditData,
+    /// `None` when DexTools has not audited the token.
+    #[serde(default)]
+    pub data: Option<TokenAuditData>,
 }
 
+/// Flags are `"yes"`, `"no"` or `"unknown"`; missing ones default to empty.
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct TokenAuditData {
     #[serde(rename = "isOpenSource")]
     pub is_open_source: String,
@@ -30,13 +34,14 @@ pub struct TokenAuditData {
     #[serde(rename = "isPotentiallyScam")]
     pub is_potentially_scam: String,
     #[serde(rename = "updatedAt")]
-    pub updated_at: String, 
+    pub updated_at: String,
 }
 
-#[derive(Debug, Default, Clone, Deserialize, Serialize)]    
+/// Tax bounds in percent; `None` when DexTools could not measure them.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Tax {
-    pub min: f64,
-    pub max: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
     pub status: String,
 }
-
@@ -1,14 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+use crate::token_amount::TokenAmount;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct HolderInfo {
     pub address: String,
-    pub balance: String,
+    pub balance: TokenAmount,
     pub username: Option<String>,
     pub profile: Option<String>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct TokenTopHolders {
     pub list: Vec<HolderInfo>,
     #[serde(rename = "totalHolders")]
@@ -16,19 +18,20 @@ pub struct TokenTopHolders {
 }
 
 // When deserializing, you'll need a custom implementation:
-impl From<Vec<(String, String, Option<String>, Option<String>)>> for TokenTopHolders {
-    fn from(list: Vec<(String, String, Option<String>, Option<String>)>) -> Self {
+impl From<Vec<(String, TokenAmount, Option<String>, Option<String>)>> for TokenTopHolders {
+    fn from(list: Vec<(String, TokenAmount, Option<String>, Option<String>)>) -> Self {
         let holders = list
             .into_iter()
             .map(|(addr, amount, username, profile)| HolderInfo {
                 address: addr,
                 balance: amount,
-                username: username,
-                profile: profile,
+                username,
+                profile,
             })
             .collect();
-        TokenTopHolders { list: holders, total_holders: "".to_string() }
+        TokenTopHolders {
+            list: holders,
+            total_holders: "".to_string(),
+        }
     }
 }
-
-
@@ -1,7 +1,11 @@
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Default, Debug, Clone,  Serialize, Deserialize)]
+use crate::token_amount::{deserialize_lenient, TokenAmount};
+
+/// Amounts the overview computes with are parsed strictly, so a malformed
+/// one fails the decode; the rest are `None` when malformed.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct TokenInfo {
     #[serde(rename = "id")]
     pub address: String,
@@ -10,9 +14,13 @@ pub struct TokenInfo {
     pub name: String,
     pub symbol: String,
     #[serde(rename = "totalSupply")]
-    pub total_supply: String,
-    #[serde(rename = "totalBurned")]
-    pub total_burned: String,
+    pub total_supply: TokenAmount,
+    #[serde(
+        rename = "totalBurned",
+        default,
+        deserialize_with = "deserialize_lenient"
+    )]
+    pub total_burned: Option<TokenAmount>,
     pub creator: String,
     #[serde(rename = "blockTimestamp")]
     pub block_timestamp: Option<String>,
@@ -26,22 +34,46 @@ pub struct TokenInfo {
     pub price: String,
 }
 
-#[derive(Default, Debug, Clone,  Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct BondingCurve {
     pub id: String,
     pub router: String,
-    #[serde(rename = "virtualAPEReserve")]
-    pub virtual_ape_reserve: String,
-    #[serde(rename = "virtualTokenReserve")]
-    pub virtual_token_reserve: String,
-    #[serde(rename = "realAPEReserve")]
-    pub real_ape_reserve: String,
-    #[serde(rename = "realTokenReserve")]
-    pub real_token_reserve: String,
-    #[serde(rename = "initialVirtualAPE")]
-    pub initial_virtual_ape: String,
-    #[serde(rename = "finalVirtualAPE")]
-    pub final_virtual_ape: String,
+    #[serde(
+        rename = "virtualAPEReserve",
+        default,
+        deserialize_with = "deserialize_lenient"
+    )]
+    pub virtual_ape_reserve: Option<TokenAmount>,
+    #[serde(
+        rename = "virtualTokenReserve",
+        default,
+        deserialize_with = "deserialize_lenient"
+    )]
+    pub virtual_token_reserve: Option<TokenAmount>,
+    #[serde(
+        rename = "realAPEReserve",
+        default,
+        deserialize_with = "deserialize_lenient"
+    )]
+    pub real_ape_reserve: Option<TokenAmount>,
+    #[serde(
+        rename = "realTokenReserve",
+        default,
+        deserialize_with = "deserialize_lenient"
+    )]
+    pub real_token_reserve: Option<TokenAmount>,
+    #[serde(
+        rename = "initialVirtualAPE",
+        default,
+        deserialize_with = "deserialize_lenient"
+    )]
+    pub initial_virtual_ape: Option<TokenAmount>,
+    #[serde(
+        rename = "finalVirtualAPE",
+        default,
+        deserialize_with = "deserialize_lenient"
+    )]
+    pub final_virtual_ape: Option<TokenAmount>,
     #[serde(rename = "tradeFeePercent")]
     pub trade_fee_percent: String,
     #[serde(rename = "totalTradeFees")]
@@ -54,7 +86,7 @@ pub struct BondingCurve {
     pub king_of_the_hill_timestamp: Option<String>,
 }
 
-#[derive(Default, Debug, Clone,  Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Details {
     pub telegram: Option<String>,
     pub twitter: Option<String>,
@@ -65,27 +97,30 @@ pub struct Details {
     pub is_profane: bool,
 }
 
-#[derive(Default, Debug, Clone,  Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub address: String,
     pub username: Option<String>,
     pub profile: Option<String>,
 }
 
-#[derive(Default, Debug, Clone,  Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Liquidity {
     pub pair: String,
     pub router: String,
     #[serde(rename = "nativeReserve")]
-    pub native_reserve: String,
+    pub native_reserve: TokenAmount,
     #[serde(rename = "tokenReserve")]
-    pub token_reserve: String,
+    pub token_reserve: TokenAmount,
     #[serde(rename = "initialNativeReserve")]
-    pub initial_native_reserve: String,
-    #[serde(rename = "initialTokenReserve")]
-    pub initial_token_reserve: String,
+    pub initial_native_reserve: TokenAmount,
+    #[serde(
+        rename = "initialTokenReserve",
+        default,
+        deserialize_with = "deserialize_lenient"
+    )]
+    pub initial_token_reserve: Option<TokenAmount>,
     #[serde(rename = "isToken0")]
     pub is_token0: bool,
     pub id: String,
 }
-
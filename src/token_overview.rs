@@ -1,11 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenOverview {
     pub data: TokenOverviewData,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenOverviewData {
     pub address: String,
     pub name: String,
@@ -14,13 +14,13 @@ pub struct TokenOverviewData {
     pub logo_url: String,
     pub description: String,
     pub decimals: i32,
-    #[serde(rename = "socialInfo")]  
+    #[serde(rename = "socialInfo")]
     pub social_info: SocialInfo,
     #[serde(rename = "creationDate")]
     pub creation_date: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SocialInfo {
     pub email: Option<String>,
     pub bitbucket: Option<String>,
@@ -29,7 +29,7 @@ pub struct SocialInfo {
     pub github: Option<String>,
     pub instagram: Option<String>,
     pub linkedin: Option<String>,
-    pub medium: Option<String>, 
+    pub medium: Option<String>,
     pub reddit: Option<String>,
     pub telegram: Option<String>,
     pub tiktok: Option<String>,
@@ -65,4 +65,3 @@ pub struct SocialInfo {
 //         }
 //     }
 // }
-
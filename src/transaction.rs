@@ -0,0 +1,60 @@
+use serde::Serialize;
+
+use crate::config::LinkConfig;
+use crate::metrics::NATIVE_DECIMALS;
+use crate::scan::TransactionData;
+
+/// A summary of one transaction, as posted when a message contains a
+/// transaction hash.
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionReport {
+    pub hash: String,
+    pub status: TransactionStatus,
+    pub from: String,
+    /// `None` for contract creations.
+    pub to: Option<String>,
+    /// APE sent along with the call, in whole APE.
+    pub value: f64,
+    /// `None` when the native APE price is unknown.
+    pub value_usd: Option<f64>,
+    /// `None` while the transaction is pending.
+    pub block: Option<u64>,
+    pub gas_used: Option<u64>,
+    /// The contract the transaction deployed, if any.
+    pub contract_created: Option<String>,
+    pub explorer_link: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    Success,
+    Failed,
+    Pending,
+}
+
+pub fn transaction_report(data: &TransactionData, links: &LinkConfig) -> TransactionReport {
+    let transaction = &data.transaction;
+    let receipt = data.receipt.as_ref();
+    let status = match receipt {
+        None => TransactionStatus::Pending,
+        Some(receipt) if receipt.status.as_deref() == Some("0x0") => TransactionStatus::Failed,
+        Some(_) => TransactionStatus::Success,
+    };
+    let value = transaction.value.to_f64(NATIVE_DECIMALS);
+    TransactionReport {
+        hash: transaction.hash.clone(),
+        status,
+        from: transaction.from.clone(),
+        to: transaction.to.clone(),
+        value,
+        value_usd: data.native_price.usd().map(|usd| value * usd),
+        block: transaction
+            .block_number
+            .as_ref()
+            .map(|block| block.raw().low_u64()),
+        gas_used: receipt.map(|receipt| receipt.gas_used.raw().low_u64()),
+        contract_created: receipt.and_then(|receipt| receipt.contract_address.clone()),
+        explorer_link: links.explorer_tx(&transaction.hash),
+    }
+}
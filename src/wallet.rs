@@ -0,0 +1,114 @@
+use std::cmp::Ordering;
+
+use serde::Serialize;
+
+use crate::config::LinkConfig;
+use crate::html::safe_url;
+use crate::metrics::{token_price_usd, NATIVE_DECIMALS};
+use crate::scan::WalletData;
+
+/// A summary of a wallet (an externally owned account), as posted when an
+/// address turns out not to be a token.
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletReport {
+    pub address: String,
+    /// The wallet's ape.express username, if it has set one.
+    pub username: Option<String>,
+    /// The wallet's ape.express profile link; `None` unless it is an
+    /// absolute http(s) URL.
+    pub profile: Option<String>,
+    /// Native balance in whole APE.
+    pub native_balance: f64,
+    /// `None` when the native APE price is unknown.
+    pub native_balance_usd: Option<f64>,
+    /// How many transactions the wallet has sent.
+    pub transaction_count: u64,
+    /// ape.express tokens held, most valuable first; `None` when
+    /// ape.express could not be reached.
+    pub positions: Option<Vec<WalletPosition>>,
+    /// APE balance plus every position with a known value, in USD; `None`
+    /// when the native APE price is unknown.
+    pub total_usd: Option<f64>,
+    pub explorer_link: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletPosition {
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+    /// Exact balance in whole tokens; `None` when the token's decimals are
+    /// unknown.
+    pub balance: Option<String>,
+    /// Priced the same way as the token overview; `None` when the native
+    /// APE price or the token's decimals are unknown.
+    pub value_usd: Option<f64>,
+    pub ape_express_link: String,
+}
+
+pub fn wallet_report(wallet: &WalletData, links: &LinkConfig) -> WalletReport {
+    let address = wallet.address.to_string();
+    let native_price_usd = wallet.native_price.usd();
+    let native_balance = wallet.balance.to_f64(NATIVE_DECIMALS);
+    let native_balance_usd = native_price_usd.map(|usd| native_balance * usd);
+
+    let positions = wallet.holdings.as_ref().map(|holdings| {
+        let mut positions: Vec<WalletPosition> = holdings
+            .holdings
+            .iter()
+            .filter(|holding| !holding.balance.is_zero())
+            .map(|holding| {
+                let token = &holding.token;
+                let decimals = wallet
+                    .token_decimals
+                    .get(&token.address.to_lowercase())
+                    .copied();
+                let price_usd = token_price_usd(token.price.parse().ok(), native_price_usd);
+                WalletPosition {
+                    address: token.address.clone(),
+                    name: token.name.clone(),
+                    symbol: token.symbol.clone(),
+                    balance: decimals.map(|decimals| holding.balance.format_units(decimals)),
+                    value_usd: decimals
+                        .zip(price_usd)
+                        .map(|(decimals, price)| holding.balance.to_f64(decimals) * price),
+                    ape_express_link: links.ape_express(&token.address),
+                }
+            })
+            .collect();
+        positions.sort_by(|a, b| {
+            b.value_usd
+                .partial_cmp(&a.value_usd)
+                .unwrap_or(Ordering::Equal)
+        });
+        positions
+    });
+
+    let total_usd = native_balance_usd.map(|native| {
+        native
+            + positions
+                .iter()
+                .flatten()
+                .filter_map(|position| position.value_usd)
+                .sum::<f64>()
+    });
+
+    let profile = wallet.holdings.as_ref();
+    WalletReport {
+        username: profile
+            .and_then(|profile| profile.username.as_deref())
+            .map(str::trim)
+            .filter(|username| !username.is_empty())
+            .map(str::to_string),
+        profile: profile
+            .and_then(|profile| profile.profile.as_deref())
+            .and_then(safe_url),
+        native_balance,
+        native_balance_usd,
+        transaction_count: wallet.transaction_count,
+        positions,
+        total_usd,
+        explorer_link: links.explorer_address(&address),
+        address,
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::token_amount::TokenAmount;
+
+/// A wallet's ape.express profile and the ape.express tokens it holds.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WalletHoldings {
+    pub address: String,
+    pub username: Option<String>,
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub holdings: Vec<Holding>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Holding {
+    pub token: HoldingToken,
+    pub balance: TokenAmount,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct HoldingToken {
+    #[serde(rename = "id")]
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+    /// Not every ape.express response includes it.
+    #[serde(default)]
+    pub decimals: Option<i32>,
+    /// Price in APE, as in [`crate::token_info::TokenInfo::price`].
+    pub price: String,
+}